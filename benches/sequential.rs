@@ -0,0 +1,37 @@
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::time::Instant;
+
+use fork_join_scope::scope;
+
+fn main() {
+    let iterations = 1_000_000;
+
+    scope(NonZeroUsize::new(1), |scope| {
+        let start = Instant::now();
+
+        for _ in 0..iterations {
+            scope.broadcast(|thread| {
+                black_box(thread);
+            });
+        }
+
+        println!(
+            "parallelism=1: broadcast took {:?} on average",
+            start.elapsed() / iterations
+        );
+
+        let start = Instant::now();
+
+        for _ in 0..iterations / 1_000 {
+            scope.iter_dynamic(0..1_000, |thread, index| {
+                black_box((thread, index));
+            });
+        }
+
+        println!(
+            "parallelism=1: iter_dynamic over 1000 indices took {:?} on average",
+            start.elapsed() / (iterations / 1_000)
+        );
+    });
+}
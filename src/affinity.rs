@@ -0,0 +1,42 @@
+#[cfg(target_os = "linux")]
+pub(crate) fn pin_current_thread(cpu: usize) -> bool {
+    use std::mem::size_of;
+
+    #[repr(C)]
+    struct CpuSet([u64; 16]);
+
+    unsafe extern "C" {
+        fn sched_setaffinity(pid: i32, cpusetsize: usize, mask: *const CpuSet) -> i32;
+    }
+
+    let mut set = CpuSet([0; 16]);
+
+    let Some(word) = set.0.get_mut(cpu / 64) else {
+        return false;
+    };
+
+    *word |= 1 << (cpu % 64);
+
+    unsafe { sched_setaffinity(0, size_of::<CpuSet>(), &set) == 0 }
+}
+
+#[cfg(windows)]
+pub(crate) fn pin_current_thread(cpu: usize) -> bool {
+    use std::ffi::c_void;
+
+    unsafe extern "system" {
+        fn GetCurrentThread() -> *mut c_void;
+        fn SetThreadAffinityMask(thread: *mut c_void, mask: usize) -> usize;
+    }
+
+    let Some(mask) = 1_usize.checked_shl(cpu as u32) else {
+        return false;
+    };
+
+    unsafe { SetThreadAffinityMask(GetCurrentThread(), mask) != 0 }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub(crate) fn pin_current_thread(_cpu: usize) -> bool {
+    false
+}
@@ -0,0 +1,89 @@
+use std::sync::atomic::Ordering;
+
+use crate::scope::{Scope, State};
+
+/// Synchronizes all threads of a broadcast, c.f. [`Scope::barrier`].
+#[derive(Clone, Copy)]
+pub struct Barrier<'scope> {
+    state: &'scope State,
+    threads: usize,
+}
+
+impl Barrier<'_> {
+    /// Blocks until all threads of the current broadcast have called this method.
+    ///
+    /// This deadlocks if any thread does not reach the barrier, e.g. because it panicked
+    /// or because it is not running the broadcast which the other threads are waiting in.
+    pub fn wait(&self) {
+        let state = self.state;
+
+        let generation = state.barrier_generation.load(Ordering::Acquire);
+
+        if state.barrier_count.fetch_add(1, Ordering::AcqRel) + 1 == self.threads {
+            state.barrier_count.store(0, Ordering::Relaxed);
+            state.barrier_generation.fetch_add(1, Ordering::Release);
+        } else {
+            let mut wait_count = 0;
+
+            while state.barrier_generation.load(Ordering::Acquire) == generation {
+                state.wait(&mut wait_count);
+            }
+        }
+    }
+}
+
+impl<'scope> Scope<'scope> {
+    /// Returns a barrier which can be passed into the closures of broadcasts of this scope.
+    pub fn barrier(&self) -> Barrier<'scope> {
+        Barrier {
+            state: self.state,
+            threads: self.workers + 1,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::AtomicUsize;
+
+    use crate::scope::scope;
+
+    use super::*;
+
+    #[test]
+    fn barrier_separates_phases() {
+        let parallelism = 4;
+
+        let phase1 = (0..parallelism)
+            .map(|_| AtomicUsize::new(0))
+            .collect::<Vec<_>>();
+
+        let sums = scope(NonZeroUsize::new(parallelism), |scope| {
+            let barrier = scope.barrier();
+
+            (0..10)
+                .map(|round| {
+                    scope.broadcast_with(|thread| {
+                        phase1[thread].store(round + thread, Ordering::Relaxed);
+
+                        barrier.wait();
+
+                        let sum = phase1
+                            .iter()
+                            .map(|value| value.load(Ordering::Relaxed))
+                            .sum::<usize>();
+
+                        barrier.wait();
+
+                        sum
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+
+        for (round, sums) in sums.into_iter().enumerate() {
+            assert!(sums.into_iter().all(|sum| sum == 4 * round + 6));
+        }
+    }
+}
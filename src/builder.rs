@@ -0,0 +1,141 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{
+    config::Config,
+    scope::{BroadcastStats, BroadcastTimeout},
+};
+#[cfg(feature = "std")]
+use crate::{pool::Pool, scope::Scope, scope::scope_with};
+
+/// Builds a [`Config`] step by step, c.f. its fields for the meaning of each option.
+#[derive(Default)]
+pub struct Builder {
+    config: Config,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parallelism(mut self, parallelism: NonZeroUsize) -> Self {
+        self.config.parallelism = Some(parallelism);
+        self
+    }
+
+    pub fn spin_limit(mut self, spin_limit: u32) -> Self {
+        self.config.spin_limit = spin_limit;
+        self
+    }
+
+    pub fn park(mut self, park: bool) -> Self {
+        self.config.park = park;
+        self
+    }
+
+    pub fn pin_threads(mut self, pin_threads: bool) -> Self {
+        self.config.pin_threads = pin_threads;
+        self
+    }
+
+    pub fn thread_name_prefix(mut self, thread_name_prefix: impl Into<String>) -> Self {
+        self.config.thread_name_prefix = thread_name_prefix.into();
+        self
+    }
+
+    pub fn stack_size(mut self, stack_size: usize) -> Self {
+        self.config.stack_size = Some(stack_size);
+        self
+    }
+
+    pub fn on_broadcast<F>(mut self, on_broadcast: F) -> Self
+    where
+        F: Fn(BroadcastStats) + Send + Sync + 'static,
+    {
+        self.config.on_broadcast = Some(Box::new(on_broadcast));
+        self
+    }
+
+    pub fn track_load(mut self, track_load: bool) -> Self {
+        self.config.track_load = track_load;
+        self
+    }
+
+    pub fn thread_init<F>(mut self, thread_init: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        self.config.thread_init = Some(Arc::new(thread_init));
+        self
+    }
+
+    pub fn thread_deinit<F>(mut self, thread_deinit: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        self.config.thread_deinit = Some(Arc::new(thread_deinit));
+        self
+    }
+
+    pub fn broadcast_timeout(mut self, broadcast_timeout: Duration) -> Self {
+        self.config.broadcast_timeout = Some(broadcast_timeout);
+        self
+    }
+
+    pub fn on_broadcast_timeout<F>(mut self, on_broadcast_timeout: F) -> Self
+    where
+        F: Fn(BroadcastTimeout) + Send + Sync + 'static,
+    {
+        self.config.on_broadcast_timeout = Some(Box::new(on_broadcast_timeout));
+        self
+    }
+
+    pub fn config(self) -> Config {
+        self.config
+    }
+
+    #[cfg(feature = "std")]
+    pub fn build_scope<F, R>(self, f: F) -> R
+    where
+        F: for<'scope> FnOnce(Scope<'scope>) -> R,
+    {
+        scope_with(self.config, f)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn build_pool(self) -> Pool {
+        Pool::new(self.config)
+    }
+}
+
+impl From<Config> for Builder {
+    fn from(config: Config) -> Self {
+        Self { config }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    #[test]
+    fn builder_configures_scope() {
+        let names = Builder::new()
+            .parallelism(NonZeroUsize::new(3).unwrap())
+            .thread_name_prefix("builder")
+            .spin_limit(2)
+            .stack_size(1 << 20)
+            .build_scope(|scope| {
+                assert_eq!(scope.parallelism(), 3);
+
+                scope.broadcast_with(|_thread| thread::current().name().map(str::to_owned))
+            });
+
+        assert_eq!(names[1].as_deref(), Some("builder-worker-1"));
+        assert_eq!(names[2].as_deref(), Some("builder-worker-2"));
+    }
+}
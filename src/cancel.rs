@@ -0,0 +1,153 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::scope::Scope;
+
+/// Stops the dynamic iterators of a scope from handing out further work once tripped.
+///
+/// Tripping is a `Release` store which the iterators check using a `Relaxed` load
+/// before claiming further work, i.e. invocations which are already running will finish.
+/// Once tripped, the token stays tripped for the remaining lifetime of the scope,
+/// or until the next call to [`Pool::scope`] or [`OwnedScope::scope`] for scopes obtained from these.
+///
+/// Only loops which call the user-supplied closure for each element or range, e.g. [`Scope::iter_dynamic`]
+/// or [`Scope::for_each_dynamic`], are stopped. Operations which compute a result from all elements,
+/// e.g. [`Scope::find_first`], [`Scope::map_dynamic`], [`Scope::fold_dynamic`] or the sorts and reductions,
+/// always process all elements.
+///
+/// [`Pool::scope`]: crate::Pool::scope
+/// [`OwnedScope::scope`]: crate::OwnedScope::scope
+#[derive(Clone, Copy)]
+pub struct CancelToken<'scope> {
+    cancelled: &'scope AtomicBool,
+}
+
+impl CancelToken<'_> {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+impl<'scope> Scope<'scope> {
+    pub fn cancel_token(&self) -> CancelToken<'scope> {
+        CancelToken {
+            cancelled: &self.state.cancelled,
+        }
+    }
+
+    /// Returns a view of this scope whose dynamic iterators ignore the [`CancelToken`].
+    ///
+    /// Used by operations built on the dynamic iterators which must not skip any elements.
+    pub(crate) fn uncancellable(&self) -> Self {
+        let mut scope = self.with_active_workers(self.workers);
+        scope.cancellable = false;
+        scope
+    }
+
+    /// Returns the flag checked by the dynamic iterators before claiming further work.
+    pub(crate) fn cancelled(&self) -> &'scope AtomicBool {
+        static NEVER: AtomicBool = AtomicBool::new(false);
+
+        if self.cancellable {
+            &self.state.cancelled
+        } else {
+            &NEVER
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    use crate::scope::scope;
+
+    #[test]
+    fn cancel_token_stops_dynamic_iteration() {
+        let processed = AtomicUsize::new(0);
+
+        scope(NonZeroUsize::new(1), |scope| {
+            let token = scope.cancel_token();
+
+            scope.iter_dynamic(0..1_000, |_thread, index| {
+                processed.fetch_add(1, Ordering::Relaxed);
+
+                if index == 100 {
+                    token.cancel();
+                }
+            });
+
+            assert!(token.is_cancelled());
+
+            scope.iter_dynamic(0..1_000, |_thread, _index| {
+                processed.fetch_add(1, Ordering::Relaxed);
+            });
+        });
+
+        assert_eq!(processed.into_inner(), 101);
+    }
+
+    #[test]
+    fn cancel_token_can_be_tripped_from_workers() {
+        scope(NonZeroUsize::new(4), |scope| {
+            let token = scope.cancel_token();
+
+            scope.broadcast(|thread| {
+                if thread == 3 {
+                    token.cancel();
+                }
+            });
+
+            assert!(token.is_cancelled());
+
+            scope.iter_dynamic_chunked(
+                0..1_000,
+                NonZeroUsize::MIN,
+                |_thread, _range| unreachable!(),
+            );
+        });
+    }
+
+    #[test]
+    fn cancel_token_does_not_affect_derived_results() {
+        scope(NonZeroUsize::new(4), |scope| {
+            scope.cancel_token().cancel();
+
+            let mut data = (0..100_000).rev().collect::<Vec<usize>>();
+            scope.sort_unstable_by(&mut data, Ord::cmp);
+            assert!(data.iter().copied().eq(0..100_000));
+
+            assert_eq!(scope.find_first(&data, |num| *num == 500), Some(500));
+
+            let mut doubled = vec![0; data.len()];
+            scope.map_dynamic(&data, &mut doubled, |num| 2 * num);
+            assert!(doubled.iter().copied().eq((0..100_000).map(|num| 2 * num)));
+
+            let sum =
+                scope.reduce_dynamic(&data, || 0, |sum, num| *sum += num, |lhs, rhs| lhs + rhs);
+            assert_eq!(sum, 100_000 * 99_999 / 2);
+
+            let sum = scope.reduce_ordered(
+                &data,
+                || 0,
+                |sum, nums| *sum += nums.iter().sum::<usize>(),
+                |lhs, rhs| lhs + rhs,
+            );
+            assert_eq!(sum, 100_000 * 99_999 / 2);
+
+            let pieces = AtomicUsize::new(0);
+            scope.split_recursive(0..data.len(), 10, |range| {
+                pieces.fetch_add(range.len(), Ordering::Relaxed);
+            });
+            assert_eq!(pieces.into_inner(), 100_000);
+
+            assert!(scope.cancel_token().is_cancelled());
+        });
+    }
+}
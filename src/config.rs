@@ -0,0 +1,94 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+#[cfg(feature = "std")]
+use std::thread::Builder;
+use std::time::Duration;
+
+use crate::scope::{BroadcastStats, BroadcastTimeout};
+
+pub struct Config {
+    /// Defaults to [`std::thread::available_parallelism`] which on Linux already
+    /// takes CPU quotas of the cgroup and the affinity mask of the process into account.
+    ///
+    /// An explicit value is used as-is.
+    pub parallelism: Option<NonZeroUsize>,
+    /// How many rounds of exponentially growing spins idle threads do before yielding or parking.
+    ///
+    /// Values above 16 are clamped to 16, i.e. at most 2^16 spins per round.
+    pub spin_limit: u32,
+    /// Park idle workers once they exhausted their spin limit instead of yielding.
+    ///
+    /// This avoids burning CPU time while the calling thread does sequential work
+    /// between broadcasts, at the cost of additional wake-up latency whenever
+    /// a broadcast has to unpark the workers, c.f. `benches/park.rs`.
+    pub park: bool,
+    /// Pin worker `thread` to CPU `thread` while the calling thread is left unpinned.
+    ///
+    /// Pinning is best-effort and failures are ignored, c.f. [`Scope::pinned_cpu`].
+    ///
+    /// [`Scope::pinned_cpu`]: crate::Scope::pinned_cpu
+    pub pin_threads: bool,
+    /// Worker threads are named `{thread_name_prefix}-worker-{thread}`.
+    pub thread_name_prefix: String,
+    /// The stack size of the worker threads.
+    ///
+    /// The calling thread which acts as thread 0 keeps its own stack, so work
+    /// recursing deeply might still overflow there even if it fits the workers' stacks.
+    pub stack_size: Option<usize>,
+    /// Called by the calling thread after each broadcast has completed.
+    ///
+    /// Broadcasts are not timed at all if this is `None`.
+    pub on_broadcast: Option<Box<dyn Fn(BroadcastStats) + Send + Sync>>,
+    /// Count the indices processed by each thread during [`Scope::iter_dynamic`], c.f. [`Scope::dynamic_load`].
+    ///
+    /// [`Scope::iter_dynamic`]: crate::Scope::iter_dynamic
+    /// [`Scope::dynamic_load`]: crate::Scope::dynamic_load
+    pub track_load: bool,
+    /// Called once on each worker with its thread index after it has started but before it runs any work.
+    ///
+    /// A panic raised here is resumed by a subsequent broadcast.
+    pub thread_init: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    /// Called once on each worker with its thread index after the scope or pool has stopped it.
+    pub thread_deinit: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    /// Report the workers which are still running a broadcast whenever this elapses.
+    ///
+    /// Reports are passed to [`Config::on_broadcast_timeout`] and, if the `tracing` feature is enabled,
+    /// emitted as warnings. The calling thread keeps waiting for the workers afterwards
+    /// as it cannot safely abandon a broadcast which is still running.
+    pub broadcast_timeout: Option<Duration>,
+    /// Called by the calling thread whenever [`Config::broadcast_timeout`] elapses during a broadcast.
+    pub on_broadcast_timeout: Option<Box<dyn Fn(BroadcastTimeout) + Send + Sync>>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            parallelism: None,
+            spin_limit: 6,
+            park: false,
+            pin_threads: false,
+            thread_name_prefix: "fork-join-scope".to_owned(),
+            stack_size: None,
+            on_broadcast: None,
+            track_load: false,
+            thread_init: None,
+            thread_deinit: None,
+            broadcast_timeout: None,
+            on_broadcast_timeout: None,
+        }
+    }
+}
+
+impl Config {
+    #[cfg(feature = "std")]
+    pub(crate) fn thread_builder(&self, thread: usize) -> Builder {
+        let mut builder =
+            Builder::new().name(format!("{}-worker-{thread}", self.thread_name_prefix));
+
+        if let Some(stack_size) = self.stack_size {
+            builder = builder.stack_size(stack_size);
+        }
+
+        builder
+    }
+}
@@ -1,190 +1,2351 @@
-use std::ops::Range;
+use std::mem::MaybeUninit;
+use std::num::NonZeroUsize;
+use std::ops::{ControlFlow, Range};
 use std::slice;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 
 use crate::{Aligned, Synced, scope::Scope};
 
+/// Generates `for_each_static_zip*` splitting all slices identically after asserting their lengths to be equal.
+macro_rules! for_each_static_zip {
+    ($name:ident, $first:ident: $First:ident, $($rest:ident: $Rest:ident),+) => {
+        pub fn $name<$First, $($Rest,)+ F>(&self, $first: &mut [$First], $($rest: &mut [$Rest],)+ f: F)
+        where
+            $First: Send,
+            $($Rest: Send,)+
+            F: Fn(&mut [$First], $(&mut [$Rest]),+) + Sync,
+        {
+            $(assert_eq!($first.len(), $rest.len());)+
+
+            let len = $first.len();
+
+            let $first = Synced($first.as_mut_ptr());
+            $(let $rest = Synced($rest.as_mut_ptr());)+
+
+            self.iter_static(0..len, |_thread, range| {
+                let $first = $first;
+                $(let $rest = $rest;)+
+
+                let $first = unsafe { slice::from_raw_parts_mut($first.0.add(range.start), range.len()) };
+                $(let $rest = unsafe { slice::from_raw_parts_mut($rest.0.add(range.start), range.len()) };)+
+
+                f($first, $($rest),+);
+            });
+        }
+    };
+}
+
 impl Scope<'_> {
+    /// Does not broadcast at all if `work` is empty.
     pub fn iter_static<F>(&self, work: Range<usize>, f: F)
     where
         F: Fn(usize, Range<usize>) + Sync,
     {
-        let work_per_thread = work.len().div_ceil(self.state.workers + 1);
+        self.iter_static_offset(work, |thread, _offset, range| f(thread, range));
+    }
+
+    /// Additionally passes the offset of each thread's range relative to `work.start`.
+    pub fn iter_static_offset<F>(&self, work: Range<usize>, f: F)
+    where
+        F: Fn(usize, usize, Range<usize>) + Sync,
+    {
+        if work.is_empty() {
+            return;
+        }
+
+        let work_per_thread = work.len().div_ceil(self.workers + 1);
+
+        self.broadcast(|thread| {
+            let offset = work.len().min(work_per_thread.saturating_mul(thread));
+
+            let start = work.start + offset;
+            let end = work.end.min(start.saturating_add(work_per_thread));
+
+            f(thread, offset, start..end);
+        });
+    }
+
+    /// Like [`Scope::iter_static`], but collects the next unprocessed index reported by `f` for each range.
+    ///
+    /// The returned vector is indexed by thread. Threads whose range is empty report its start,
+    /// so does every thread if `work` is empty as nothing is broadcast then.
+    pub fn iter_static_resumable<F>(&self, work: Range<usize>, f: F) -> Vec<usize>
+    where
+        F: Fn(usize, Range<usize>) -> usize + Sync,
+    {
+        if work.is_empty() {
+            return vec![work.start; self.workers + 1];
+        }
+
+        let work_per_thread = work.len().div_ceil(self.workers + 1);
+
+        self.broadcast_with(|thread| {
+            let offset = work.len().min(work_per_thread.saturating_mul(thread));
+
+            let start = work.start + offset;
+            let end = work.end.min(start.saturating_add(work_per_thread));
+
+            if start == end {
+                return start;
+            }
+
+            let next = f(thread, start..end);
+
+            debug_assert!(
+                (start..=end).contains(&next),
+                "next unprocessed index {next} is outside of {start}..={end}"
+            );
+
+            next
+        })
+    }
+
+    /// Like [`Scope::iter_static`], but for index spaces which might exceed `usize` on 32-bit targets.
+    pub fn iter_static_u64<F>(&self, work: Range<u64>, f: F)
+    where
+        F: Fn(usize, Range<u64>) + Sync,
+    {
+        if work.is_empty() {
+            return;
+        }
+
+        let len = work.end - work.start;
+        let work_per_thread = len.div_ceil(self.workers as u64 + 1);
 
         self.broadcast(|thread| {
-            let start = work.start + work_per_thread * thread;
-            let end = work.end.min(start + work_per_thread);
+            let offset = len.min(work_per_thread.saturating_mul(thread as u64));
+
+            let start = work.start + offset;
+            let end = work.end.min(start.saturating_add(work_per_thread));
 
             f(thread, start..end);
         });
     }
 
+    /// Splits the rows of a `rows` by `cols` grid across the threads, passing all columns to each.
+    pub fn tiles_2d<F>(&self, rows: usize, cols: usize, f: F)
+    where
+        F: Fn(usize, Range<usize>, Range<usize>) + Sync,
+    {
+        if cols == 0 {
+            return;
+        }
+
+        self.iter_static(0..rows, |thread, rows| f(thread, rows, 0..cols));
+    }
+
     pub fn for_each_static<T, F>(&self, work: &mut [T], f: F)
     where
         T: Send,
         F: Fn(&mut [T]) + Sync,
+    {
+        self.for_each_static_indexed(work, |_range, work| f(work));
+    }
+
+    pub fn for_each_static_indexed<T, F>(&self, work: &mut [T], f: F)
+    where
+        T: Send,
+        F: Fn(Range<usize>, &mut [T]) + Sync,
     {
         let work_ptr = Synced(work.as_mut_ptr());
 
+        #[cfg(debug_assertions)]
+        let claims = Claims::new(work.len());
+
         self.iter_static(0..work.len(), |_thread, range| {
             let work_ptr = work_ptr;
 
+            #[cfg(debug_assertions)]
+            claims.claim(_thread, range.clone());
+
             let work =
                 unsafe { slice::from_raw_parts_mut(work_ptr.0.add(range.start), range.len()) };
 
-            f(work);
+            f(range, work);
         });
     }
 
-    pub fn fold_static<T, A, F>(&self, work: &[T], accum: &mut Vec<Aligned<A>>, f: F)
+    /// Like [`Scope::for_each_static_indexed`], but for uninitialized memory, passing the offset of each chunk.
+    ///
+    /// The caller must not assume `data` to be initialized afterwards unless `f` initializes
+    /// every element of every chunk, c.f. [`Scope::extend_static`].
+    pub fn init_static<T, F>(&self, data: &mut [MaybeUninit<T>], f: F)
     where
         T: Send,
-        A: Default + Send,
-        F: Fn(&mut A, &[T]) + Sync,
+        F: Fn(usize, &mut [MaybeUninit<T>]) + Sync,
     {
-        accum.clear();
-        accum.resize_with(self.state.workers + 1, Default::default);
+        self.for_each_static_indexed(data, |range, data| f(range.start, data));
+    }
 
-        let work_ptr = Synced(work.as_ptr());
-        let accum_ptr = Synced(accum.as_mut_ptr());
+    for_each_static_zip!(for_each_static_zip, a: A, b: B);
+
+    for_each_static_zip!(for_each_static_zip3, a: A, b: B, c: C);
+
+    for_each_static_zip!(for_each_static_zip4, a: A, b: B, c: C, d: D);
+
+    /// Splits the `rows` across the threads and passes each row with its index to `f`.
+    ///
+    /// The rows are split by their number, not by their lengths, so rows of very different lengths
+    /// might be better served by [`Scope::for_each_dynamic_indexed`].
+    pub fn for_each_rows<T, F>(&self, rows: &mut [Vec<T>], f: F)
+    where
+        T: Send,
+        F: Fn(usize, &mut [T]) + Sync,
+    {
+        let rows_ptr = Synced(rows.as_mut_ptr());
+
+        self.iter_static(0..rows.len(), |_thread, range| {
+            let rows_ptr = rows_ptr;
+
+            for index in range {
+                let row = unsafe { &mut *rows_ptr.0.add(index) };
+
+                f(index, row);
+            }
+        });
+    }
+
+    /// Calls `init` once per thread before processing its chunk and `finish` afterwards.
+    pub fn for_each_static_with<T, S, I, D, F>(&self, work: &mut [T], init: I, finish: D, f: F)
+    where
+        T: Send,
+        I: Fn(usize) -> S + Sync,
+        D: Fn(S) + Sync,
+        F: Fn(&mut S, &mut [T]) + Sync,
+    {
+        let work_ptr = Synced(work.as_mut_ptr());
 
         self.iter_static(0..work.len(), |thread, range| {
             let work_ptr = work_ptr;
-            let accum_ptr = accum_ptr;
 
-            let work = unsafe { slice::from_raw_parts(work_ptr.0.add(range.start), range.len()) };
-            let accum = unsafe { &mut *accum_ptr.0.add(thread) };
+            let work =
+                unsafe { slice::from_raw_parts_mut(work_ptr.0.add(range.start), range.len()) };
 
-            f(accum, work);
+            let mut state = init(thread);
+
+            f(&mut state, work);
+
+            finish(state);
         });
     }
 
-    pub fn iter_dynamic<F>(&self, work: Range<usize>, f: F)
+    /// Splits `data` such that every chunk except the first starts at an address aligned to `align`.
+    ///
+    /// The first chunk therefore also covers any unaligned prefix.
+    pub fn for_each_static_aligned<F>(&self, data: &mut [u8], align: usize, f: F)
     where
-        F: Fn(usize, usize) + Sync,
+        F: Fn(&mut [u8]) + Sync,
     {
-        let next_index = AtomicUsize::new(work.start + self.state.workers + 1);
+        assert_ne!(align, 0);
+
+        if data.is_empty() {
+            return;
+        }
+
+        let len = data.len();
+        let base = data.as_ptr().addr();
+        let work_per_thread = len.div_ceil(self.workers + 1);
+
+        let split = |thread: usize| {
+            if thread == 0 {
+                return 0;
+            }
+
+            let offset = len.min(work_per_thread.saturating_mul(thread));
+
+            (base + offset)
+                .checked_next_multiple_of(align)
+                .map_or(len, |addr| len.min(addr - base))
+        };
+
+        let data_ptr = Synced(data.as_mut_ptr());
 
         self.broadcast(|thread| {
-            let mut index = work.start + thread;
+            let data_ptr = data_ptr;
 
-            loop {
-                if index >= work.end {
-                    return;
-                }
+            let start = split(thread);
+            let end = split(thread + 1);
 
-                f(thread, index);
+            let data = unsafe { slice::from_raw_parts_mut(data_ptr.0.add(start), end - start) };
 
-                index = next_index.fetch_add(1, Ordering::Relaxed);
-            }
+            f(data);
         });
     }
 
-    pub fn for_each_dynamic<T, F>(&self, work: &mut [T], f: F)
+    /// Splits `data` into chunks whose lengths are multiples of `lane`, leaving the remainder to `tail`.
+    ///
+    /// The whole lanes are divided across the threads like [`Scope::iter_static`] divides indices,
+    /// i.e. all split points are rounded to multiples of `lane` from the start of `data`.
+    /// The remaining `data.len() % lane` elements are passed to `tail` on the calling thread
+    /// after all chunks have been processed, even if there are none.
+    pub fn chunks_exact_static<T, F, R>(&self, data: &mut [T], lane: usize, f: F, tail: R)
     where
         T: Send,
-        F: Fn(&mut T) + Sync,
+        F: Fn(&mut [T]) + Sync,
+        R: FnOnce(&mut [T]),
     {
-        let work_ptr = Synced(work.as_mut_ptr());
+        assert_ne!(lane, 0);
 
-        self.iter_dynamic(0..work.len(), |_thread, index| {
-            let work_ptr = work_ptr;
+        let lanes = data.len() / lane;
+        let (body, rest) = data.split_at_mut(lanes * lane);
 
-            let work = unsafe { &mut *work_ptr.0.add(index) };
+        let body_ptr = Synced(body.as_mut_ptr());
+
+        self.iter_static(0..lanes, |_thread, range| {
+            let body_ptr = body_ptr;
+
+            let body = unsafe {
+                slice::from_raw_parts_mut(body_ptr.0.add(range.start * lane), range.len() * lane)
+            };
 
-            f(work);
+            f(body);
         });
+
+        tail(rest);
     }
 
-    pub fn fold_dynamic<T, A, F>(&self, work: &[T], accum: &mut Vec<Aligned<A>>, f: F)
+    pub fn chunks_static<T, F>(&self, data: &[T], f: F)
+    where
+        T: Sync,
+        F: Fn(usize, &[T]) + Sync,
+    {
+        self.iter_static(0..data.len(), |thread, range| f(thread, &data[range]));
+    }
+
+    /// Passes each thread's owned range together with a view of `data` extended by `halo` elements on either side.
+    ///
+    /// The halo is clamped at the boundaries of `data`, so the owned range starts at `range.start.min(halo)` within the view.
+    pub fn for_each_static_halo<T, F>(&self, data: &[T], halo: usize, f: F)
+    where
+        T: Sync,
+        F: Fn(Range<usize>, &[T]) + Sync,
+    {
+        self.iter_static(0..data.len(), |_thread, range| {
+            let start = range.start.saturating_sub(halo);
+            let end = data.len().min(range.end.saturating_add(halo));
+
+            f(range, &data[start..end]);
+        });
+    }
+
+    /// Returns one output per thread, even if its chunk is empty.
+    pub fn map_chunks_static<T, U, F>(&self, data: &[T], f: F) -> Vec<U>
+    where
+        T: Sync,
+        U: Send,
+        F: Fn(usize, &[T]) -> U + Sync,
+    {
+        let work_per_thread = data.len().div_ceil(self.workers + 1);
+
+        self.broadcast_with(|thread| {
+            let start = data.len().min(work_per_thread.saturating_mul(thread));
+            let end = data.len().min(start.saturating_add(work_per_thread));
+
+            f(thread, &data[start..end])
+        })
+    }
+
+    pub fn fold_static<T, A, F>(&self, work: &[T], accum: &mut Vec<Aligned<A>>, f: F)
     where
         T: Send,
         A: Default + Send,
-        F: Fn(&mut A, &T) + Sync,
+        F: Fn(&mut A, &[T]) + Sync,
     {
         accum.clear();
-        accum.resize_with(self.state.workers + 1, Default::default);
+        accum.resize_with(self.workers + 1, Default::default);
+
+        self.fold_static_into(work, accum, f);
+    }
+
+    /// Resets the given accumulators in place instead of reallocating them like [`Scope::fold_static`].
+    pub fn fold_static_reuse<T, A, F>(&self, work: &[T], accum: &mut [Aligned<A>], f: F)
+    where
+        T: Send,
+        A: Default + Send,
+        F: Fn(&mut A, &[T]) + Sync,
+    {
+        assert_eq!(
+            accum.len(),
+            self.workers + 1,
+            "accumulators must be sized for the parallelism of the scope"
+        );
+
+        for accum in &mut *accum {
+            **accum = A::default();
+        }
+
+        self.fold_static_into(work, accum, f);
+    }
+
+    /// Continues folding into the given accumulators instead of resetting them.
+    pub fn fold_static_into<T, A, F>(&self, work: &[T], accum: &mut [Aligned<A>], f: F)
+    where
+        T: Send,
+        A: Send,
+        F: Fn(&mut A, &[T]) + Sync,
+    {
+        assert_eq!(accum.len(), self.workers + 1);
 
         let work_ptr = Synced(work.as_ptr());
         let accum_ptr = Synced(accum.as_mut_ptr());
 
-        self.iter_dynamic(0..work.len(), |thread, index| {
+        self.iter_static(0..work.len(), |thread, range| {
             let work_ptr = work_ptr;
             let accum_ptr = accum_ptr;
 
-            let work = unsafe { &*work_ptr.0.add(index) };
+            let work = unsafe { slice::from_raw_parts(work_ptr.0.add(range.start), range.len()) };
             let accum = unsafe { &mut *accum_ptr.0.add(thread) };
 
             f(accum, work);
         });
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::scope::scope;
+    /// Stops folding as soon as `f` fails for any element.
+    ///
+    /// If several elements fail, the error that is returned is the first one
+    /// reported by any thread. Partially computed accumulators are discarded.
+    pub fn try_fold_static<T, A, E, F>(&self, work: &[T], f: F) -> Result<Vec<Aligned<A>>, E>
+    where
+        T: Send,
+        A: Default + Send,
+        E: Send,
+        F: Fn(&mut A, &T) -> Result<(), E> + Sync,
+    {
+        let failed = AtomicBool::new(false);
+        let error = Mutex::new(None);
 
-    #[test]
-    fn for_each_static_works() {
-        let length = 1_000;
+        let mut accum = Vec::new();
 
-        let mut counts = (0..length).map(|_| 0).collect::<Vec<_>>();
+        self.fold_static(work, &mut accum, |accum, work| {
+            for work in work {
+                if failed.load(Ordering::Relaxed) {
+                    return;
+                }
 
-        scope(None, |scope| {
-            scope.for_each_static(&mut counts, |counts| {
-                for count in counts {
-                    *count += 1;
+                if let Err(err) = f(accum, work) {
+                    failed.store(true, Ordering::Relaxed);
+                    error.lock().unwrap().get_or_insert(err);
+                    return;
                 }
-            });
+            }
         });
 
-        for count in counts {
-            assert_eq!(count, 1);
+        match error.into_inner().unwrap() {
+            Some(err) => Err(err),
+            None => Ok(accum),
         }
     }
 
-    #[test]
-    fn fold_static_works() {
-        let length = 1_000;
+    pub fn map_static<T, U, F>(&self, input: &[T], output: &mut [U], f: F)
+    where
+        T: Sync,
+        U: Send,
+        F: Fn(&[T], &mut [U]) + Sync,
+    {
+        assert_eq!(input.len(), output.len());
 
-        let nums = (0..length).map(|index| index).collect::<Vec<_>>();
-        let mut sums = Vec::new();
+        let output_ptr = Synced(output.as_mut_ptr());
 
-        scope(None, |scope| {
-            scope.fold_static(&nums, &mut sums, |sum: &mut usize, nums| {
-                for num in nums {
-                    *sum += num;
+        #[cfg(debug_assertions)]
+        let claims = Claims::new(output.len());
+
+        self.iter_static(0..input.len(), |_thread, range| {
+            let output_ptr = output_ptr;
+
+            #[cfg(debug_assertions)]
+            claims.claim(_thread, range.clone());
+
+            let output =
+                unsafe { slice::from_raw_parts_mut(output_ptr.0.add(range.start), range.len()) };
+
+            f(&input[range], output);
+        });
+    }
+
+    pub fn iter_dynamic<F>(&self, work: Range<usize>, f: F)
+    where
+        F: Fn(usize, usize) + Sync,
+    {
+        self.iter_dynamic_impl(work, |thread| thread, |thread, index| f(*thread, index));
+    }
+
+    /// Like [`Scope::iter_dynamic`], but passes per-thread state created once by `init` instead of the thread index.
+    fn iter_dynamic_impl<S, I, F>(&self, work: Range<usize>, init: I, f: F)
+    where
+        I: Fn(usize) -> S + Sync,
+        F: Fn(&mut S, usize) + Sync,
+    {
+        let cancelled = self.cancelled();
+
+        let load = &self.state.load;
+        let track_load = !load.is_empty();
+
+        if track_load {
+            self.state
+                .load_threads
+                .store(self.workers + 1, Ordering::Relaxed);
+        }
+
+        if self.workers == 0 {
+            return self.broadcast(|thread| {
+                let mut state = init(thread);
+                let mut count = 0;
+
+                for index in work.clone() {
+                    if cancelled.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    f(&mut state, index);
+
+                    count += 1;
+                }
+
+                if track_load {
+                    load[thread].store(count, Ordering::Relaxed);
                 }
             });
+        }
+
+        let next_index = AtomicUsize::new(work.start + self.workers + 1);
+
+        self.broadcast(|thread| {
+            let mut state = init(thread);
+            let mut index = work.start + thread;
+            let mut count = 0;
+
+            while index < work.end && !cancelled.load(Ordering::Relaxed) {
+                f(&mut state, index);
+
+                count += 1;
+
+                index = next_index.fetch_add(1, Ordering::Relaxed);
+            }
+
+            if track_load {
+                load[thread].store(count, Ordering::Relaxed);
+            }
         });
+    }
 
-        let sum: usize = sums.into_iter().map(|sum| sum.0).sum();
+    /// Summarizes how many indices each thread processed during the most recent [`Scope::iter_dynamic`]
+    /// or [`Scope::fold_dynamic`].
+    ///
+    /// Returns `None` unless [`Config::track_load`] is set and `iter_dynamic` was called at least once.
+    ///
+    /// [`Config::track_load`]: crate::Config::track_load
+    pub fn dynamic_load(&self) -> Option<LoadStats> {
+        let threads = self.state.load_threads.load(Ordering::Relaxed);
 
-        assert_eq!(sum, length * (length - 1) / 2);
+        if threads == 0 {
+            return None;
+        }
+
+        let counts = self.state.load[..threads]
+            .iter()
+            .map(|count| count.load(Ordering::Relaxed));
+
+        let total = counts.clone().sum::<usize>();
+
+        Some(LoadStats {
+            min: counts.clone().min().unwrap(),
+            max: counts.max().unwrap(),
+            avg: total as f64 / threads as f64,
+            total,
+        })
     }
 
-    #[test]
-    fn for_each_dynamic_works() {
-        let length = 1_000;
+    /// Like [`Scope::iter_dynamic`], but for index spaces which might exceed `usize` on 32-bit targets.
+    pub fn iter_dynamic_u64<F>(&self, work: Range<u64>, f: F)
+    where
+        F: Fn(usize, u64) + Sync,
+    {
+        let cancelled = self.cancelled();
 
-        let mut counts = (0..length).map(|_| 0).collect::<Vec<_>>();
+        if self.workers == 0 {
+            return self.broadcast(|thread| {
+                for index in work.clone() {
+                    if cancelled.load(Ordering::Relaxed) {
+                        return;
+                    }
 
-        scope(None, |scope| {
-            scope.for_each_dynamic(&mut counts, |count| {
-                *count += 1;
+                    f(thread, index);
+                }
             });
+        }
+
+        let next_index = AtomicU64::new(work.start + self.workers as u64 + 1);
+
+        self.broadcast(|thread| {
+            let mut index = work.start + thread as u64;
+
+            loop {
+                if index >= work.end || cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                f(thread, index);
+
+                index = next_index.fetch_add(1, Ordering::Relaxed);
+            }
         });
+    }
 
-        for count in counts {
-            assert_eq!(count, 1);
-        }
+    /// Like [`Scope::iter_dynamic`], but claims each thread's next index before calling `f` on the current one
+    /// and prefetches the data at `address(next)` into the cache meanwhile.
+    ///
+    /// Prefetching is only implemented on x86-64 and a no-op elsewhere. `address` is never dereferenced.
+    #[cfg(feature = "prefetch")]
+    pub fn iter_dynamic_prefetch<T, A, F>(&self, work: Range<usize>, address: A, f: F)
+    where
+        A: Fn(usize) -> *const T + Sync,
+        F: Fn(usize, usize) + Sync,
+    {
+        let cancelled = self.cancelled();
+
+        if self.workers == 0 {
+            return self.broadcast(|thread| {
+                for index in work.clone() {
+                    if cancelled.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    if index + 1 < work.end {
+                        prefetch(address(index + 1));
+                    }
+
+                    f(thread, index);
+                }
+            });
+        }
+
+        let next_index = AtomicUsize::new(work.start + self.workers + 1);
+
+        self.broadcast(|thread| {
+            let mut index = work.start + thread;
+
+            while index < work.end && !cancelled.load(Ordering::Relaxed) {
+                let next = next_index.fetch_add(1, Ordering::Relaxed);
+
+                if next < work.end {
+                    prefetch(address(next));
+                }
+
+                f(thread, index);
+
+                index = next;
+            }
+        });
+    }
+
+    /// Stops handing out further indices once any invocation of `f` breaks.
+    ///
+    /// Invocations which are already running are allowed to finish.
+    pub fn iter_dynamic_until<F>(&self, work: Range<usize>, f: F)
+    where
+        F: Fn(usize, usize) -> ControlFlow<()> + Sync,
+    {
+        let next_index = AtomicUsize::new(work.start + self.workers + 1);
+        let cancelled = self.cancelled();
+
+        self.broadcast(|thread| {
+            let mut index = work.start + thread;
+
+            loop {
+                if index >= work.end || cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                if f(thread, index).is_break() {
+                    next_index.store(work.end, Ordering::Relaxed);
+                    return;
+                }
+
+                index = next_index.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+    }
+
+    pub fn find_first<T, F>(&self, work: &[T], pred: F) -> Option<usize>
+    where
+        T: Sync,
+        F: Fn(&T) -> bool + Sync,
+    {
+        let first_index = AtomicUsize::new(usize::MAX);
+
+        self.uncancellable()
+            .iter_dynamic_until(0..work.len(), |_thread, index| {
+                if index > first_index.load(Ordering::Relaxed) {
+                    return ControlFlow::Break(());
+                }
+
+                if pred(&work[index]) {
+                    first_index.fetch_min(index, Ordering::Relaxed);
+                    return ControlFlow::Break(());
+                }
+
+                ControlFlow::Continue(())
+            });
+
+        match first_index.into_inner() {
+            usize::MAX => None,
+            index => Some(index),
+        }
+    }
+
+    pub fn iter_dynamic_chunked<F>(&self, work: Range<usize>, chunk: NonZeroUsize, f: F)
+    where
+        F: Fn(usize, Range<usize>) + Sync,
+    {
+        let chunk = chunk.get().min(work.len().max(1));
+
+        let next_start = AtomicUsize::new(
+            work.start
+                .saturating_add((self.workers + 1).saturating_mul(chunk)),
+        );
+        let cancelled = self.cancelled();
+
+        self.broadcast(|thread| {
+            let mut start = work.start.saturating_add(thread.saturating_mul(chunk));
+
+            loop {
+                if start >= work.end || cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                let end = work.end.min(start + chunk);
+
+                f(thread, start..end);
+
+                start = next_start.fetch_add(chunk, Ordering::Relaxed);
+            }
+        });
+    }
+
+    /// Splits the first half of `work` statically and hands out the second half dynamically,
+    /// i.e. each thread only contends for indices after it has finished its static portion.
+    pub fn iter_adaptive<F>(&self, work: Range<usize>, f: F)
+    where
+        F: Fn(usize, usize) + Sync,
+    {
+        if work.is_empty() {
+            return;
+        }
+
+        let overflow_start = work.start + work.len() / 2;
+        let static_per_thread = (overflow_start - work.start).div_ceil(self.workers + 1);
+
+        let next_index = AtomicUsize::new(overflow_start);
+        let cancelled = self.cancelled();
+
+        self.broadcast(|thread| {
+            let static_start = overflow_start.min(work.start + static_per_thread * thread);
+            let static_end = overflow_start.min(static_start + static_per_thread);
+
+            for index in static_start..static_end {
+                f(thread, index);
+            }
+
+            loop {
+                if cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+
+                if index >= work.end {
+                    return;
+                }
+
+                f(thread, index);
+            }
+        });
+    }
+
+    /// Claims chunks proportional to the remaining work divided by the number of threads,
+    /// i.e. chunks start out large and shrink down to single indices near the end.
+    pub fn iter_guided<F>(&self, work: Range<usize>, f: F)
+    where
+        F: Fn(usize, Range<usize>) + Sync,
+    {
+        let threads = self.workers + 1;
+
+        let next_start = AtomicUsize::new(work.start);
+        let cancelled = self.cancelled();
+
+        self.broadcast(|thread| {
+            let mut start = next_start.load(Ordering::Relaxed);
+
+            loop {
+                if start >= work.end || cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                let chunk = ((work.end - start) / threads).max(1);
+
+                match next_start.compare_exchange_weak(
+                    start,
+                    start + chunk,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        f(thread, start..start + chunk);
+
+                        start = next_start.load(Ordering::Relaxed);
+                    }
+                    Err(curr_start) => start = curr_start,
+                }
+            }
+        });
+    }
+
+    /// Claims chunks which start at half of each thread's static share
+    /// and halve with each subsequent claim by the same thread down to single indices.
+    pub fn iter_dynamic_tapered<F>(&self, work: Range<usize>, f: F)
+    where
+        F: Fn(usize, Range<usize>) + Sync,
+    {
+        let initial_chunk = work.len().div_ceil(2 * (self.workers + 1)).max(1);
+
+        let next_start = AtomicUsize::new(work.start);
+        let cancelled = self.cancelled();
+
+        self.broadcast(|thread| {
+            let mut chunk = initial_chunk;
+
+            loop {
+                if cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                let start = next_start.fetch_add(chunk, Ordering::Relaxed);
+
+                if start >= work.end {
+                    return;
+                }
+
+                let end = work.end.min(start.saturating_add(chunk));
+
+                f(thread, start..end);
+
+                chunk = (chunk / 2).max(1);
+            }
+        });
+    }
+
+    /// Starts each thread on its static range and lets threads which are done steal from the back
+    /// of the other threads' ranges.
+    ///
+    /// Falls back to [`Scope::iter_dynamic`] if the ranges do not fit into 32 bits.
+    pub fn iter_stealing<F>(&self, work: Range<usize>, f: F)
+    where
+        F: Fn(usize, usize) + Sync,
+    {
+        if work.is_empty() {
+            return;
+        }
+
+        let threads = self.workers + 1;
+        let work_per_thread = work.len().div_ceil(threads);
+
+        if work_per_thread > u32::MAX as usize {
+            return self.iter_dynamic(work, f);
+        }
+
+        let start =
+            |thread: usize| work.start + work.len().min(work_per_thread.saturating_mul(thread));
+
+        // The front and back offsets into each thread's range are packed into a single atomic
+        // so that the owner and the thieves can claim indices using a single compare-and-swap.
+        let bounds = (0..threads)
+            .map(|thread| Aligned(AtomicU64::new((start(thread + 1) - start(thread)) as u64)))
+            .collect::<Vec<_>>();
+
+        let cancelled = self.cancelled();
+
+        self.broadcast(|thread| {
+            for victim in (thread..threads).chain(0..thread) {
+                let base = start(victim);
+                let bounds = &bounds[victim];
+
+                let mut curr_bounds = bounds.load(Ordering::Relaxed);
+
+                loop {
+                    if cancelled.load(Ordering::Relaxed) {
+                        return;
+                    }
+
+                    let (front, back) = (curr_bounds >> 32, curr_bounds & u64::from(u32::MAX));
+
+                    if front >= back {
+                        break;
+                    }
+
+                    let (next_bounds, offset) = if victim == thread {
+                        (curr_bounds + (1 << 32), front)
+                    } else {
+                        (curr_bounds - 1, back - 1)
+                    };
+
+                    match bounds.compare_exchange_weak(
+                        curr_bounds,
+                        next_bounds,
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(_) => {
+                            f(thread, base + offset as usize);
+
+                            curr_bounds = bounds.load(Ordering::Relaxed);
+                        }
+                        Err(bounds) => curr_bounds = bounds,
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn for_each_dynamic<T, F>(&self, work: &mut [T], f: F)
+    where
+        T: Send,
+        F: Fn(&mut T) + Sync,
+    {
+        self.for_each_dynamic_indexed(work, |_index, work| f(work));
+    }
+
+    pub fn for_each_dynamic_indexed<T, F>(&self, work: &mut [T], f: F)
+    where
+        T: Send,
+        F: Fn(usize, &mut T) + Sync,
+    {
+        let work_ptr = Synced(work.as_mut_ptr());
+
+        self.iter_dynamic(0..work.len(), |_thread, index| {
+            let work_ptr = work_ptr;
+
+            let work = unsafe { &mut *work_ptr.0.add(index) };
+
+            f(index, work);
+        });
+    }
+
+    pub fn for_each_dynamic_zip<A, B, F>(&self, a: &mut [A], b: &mut [B], f: F)
+    where
+        A: Send,
+        B: Send,
+        F: Fn(&mut A, &mut B) + Sync,
+    {
+        assert_eq!(a.len(), b.len());
+
+        let a_ptr = Synced(a.as_mut_ptr());
+        let b_ptr = Synced(b.as_mut_ptr());
+
+        self.iter_dynamic(0..a.len(), |_thread, index| {
+            let a_ptr = a_ptr;
+            let b_ptr = b_ptr;
+
+            let a = unsafe { &mut *a_ptr.0.add(index) };
+            let b = unsafe { &mut *b_ptr.0.add(index) };
+
+            f(a, b);
+        });
+    }
+
+    /// Stops handing out further elements once `f` fails.
+    ///
+    /// If several elements fail, the error that is returned is the first one
+    /// reported by any thread, which is not necessarily the one at the lowest index.
+    pub fn try_for_each_dynamic<T, E, F>(&self, work: &mut [T], f: F) -> Result<(), E>
+    where
+        T: Send,
+        E: Send,
+        F: Fn(&mut T) -> Result<(), E> + Sync,
+    {
+        let work_ptr = Synced(work.as_mut_ptr());
+        let error = Mutex::new(None);
+
+        self.iter_dynamic_until(0..work.len(), |_thread, index| {
+            let work_ptr = work_ptr;
+
+            let work = unsafe { &mut *work_ptr.0.add(index) };
+
+            match f(work) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(err) => {
+                    error.lock().unwrap().get_or_insert(err);
+                    ControlFlow::Break(())
+                }
+            }
+        });
+
+        error.into_inner().unwrap().map_or(Ok(()), Err)
+    }
+
+    pub fn for_each_dynamic_with<T, L, I, F>(
+        &self,
+        work: &mut [T],
+        init: I,
+        f: F,
+    ) -> Vec<Aligned<L>>
+    where
+        T: Send,
+        L: Send,
+        I: Fn() -> L,
+        F: Fn(&mut L, &mut T) + Sync,
+    {
+        let mut locals = (0..=self.workers)
+            .map(|_| Aligned(init()))
+            .collect::<Vec<_>>();
+
+        let work_ptr = Synced(work.as_mut_ptr());
+        let locals_ptr = Synced(locals.as_mut_ptr());
+
+        self.iter_dynamic(0..work.len(), |thread, index| {
+            let work_ptr = work_ptr;
+            let locals_ptr = locals_ptr;
+
+            let work = unsafe { &mut *work_ptr.0.add(index) };
+            let local = unsafe { &mut *locals_ptr.0.add(thread) };
+
+            f(local, work);
+        });
+
+        locals
+    }
+
+    pub fn map_dynamic<T, U, F>(&self, input: &[T], output: &mut [U], f: F)
+    where
+        T: Sync,
+        U: Send,
+        F: Fn(&T) -> U + Sync,
+    {
+        assert_eq!(input.len(), output.len());
+
+        if input.is_empty() {
+            return;
+        }
+
+        let output_ptr = Synced(output.as_mut_ptr());
+
+        self.uncancellable()
+            .iter_dynamic(0..input.len(), |_thread, index| {
+                let output_ptr = output_ptr;
+
+                let output = unsafe { &mut *output_ptr.0.add(index) };
+
+                *output = f(&input[index]);
+            });
+    }
+
+    pub fn fold_dynamic<T, A, F>(&self, work: &[T], accum: &mut Vec<Aligned<A>>, f: F)
+    where
+        T: Send,
+        A: Default + Send,
+        F: Fn(&mut A, &T) + Sync,
+    {
+        accum.clear();
+        accum.resize_with(self.workers + 1, Default::default);
+
+        let work_ptr = Synced(work.as_ptr());
+        let accum_ptr = Synced(accum.as_mut_ptr());
+
+        // Obtains the accumulator once per thread instead of once per element.
+        self.uncancellable().iter_dynamic_impl(
+            0..work.len(),
+            |thread| {
+                let accum_ptr = accum_ptr;
+
+                // SAFETY: Each thread has exclusive access to its own accumulator.
+                unsafe { &mut *accum_ptr.0.add(thread) }
+            },
+            |accum, index| {
+                let work_ptr = work_ptr;
+
+                let work = unsafe { &*work_ptr.0.add(index) };
+
+                f(accum, work);
+            },
+        );
+    }
+}
+
+#[cfg(feature = "prefetch")]
+#[inline(always)]
+fn prefetch<T>(ptr: *const T) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        use std::arch::x86_64::{_MM_HINT_T0, _mm_prefetch};
+
+        // SAFETY: Prefetching is only a hint and never faults, even for invalid addresses.
+        unsafe { _mm_prefetch::<_MM_HINT_T0>(ptr.cast()) };
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = ptr;
+}
+
+/// Returned by [`Scope::dynamic_load`].
+pub struct LoadStats {
+    /// The minimum number of indices processed by any thread.
+    pub min: usize,
+    /// The maximum number of indices processed by any thread.
+    pub max: usize,
+    /// The average number of indices processed per thread.
+    pub avg: f64,
+    /// The total number of indices processed by all threads.
+    pub total: usize,
+}
+
+/// Records the range handed to each thread to catch overlapping chunks in debug builds.
+///
+/// Only the boundaries of the ranges are compared, so this costs O(threads) instead of O(elements) per call.
+#[cfg(debug_assertions)]
+struct Claims {
+    len: usize,
+    ranges: Mutex<Vec<(usize, Range<usize>)>>,
+}
+
+#[cfg(debug_assertions)]
+impl Claims {
+    fn new(len: usize) -> Self {
+        Self {
+            len,
+            ranges: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn claim(&self, thread: usize, range: Range<usize>) {
+        assert!(
+            range.end <= self.len,
+            "range {range:?} was handed to thread {thread} but there are only {} elements",
+            self.len
+        );
+
+        let mut ranges = self.ranges.lock().unwrap();
+
+        for (other_thread, other_range) in &*ranges {
+            let start = range.start.max(other_range.start);
+
+            assert!(
+                start >= range.end.min(other_range.end),
+                "element {start} was handed to thread {thread} and thread {other_thread}"
+            );
+        }
+
+        ranges.push((thread, range));
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::ops::ControlFlow;
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::{
+        Aligned, Config,
+        scope::{scope, scope_with},
+    };
+
+    #[test]
+    fn for_each_static_works() {
+        let length = 1_000;
+
+        let mut counts = (0..length).map(|_| 0).collect::<Vec<_>>();
+
+        scope(None, |scope| {
+            scope.for_each_static(&mut counts, |counts| {
+                for count in counts {
+                    *count += 1;
+                }
+            });
+        });
+
+        for count in counts {
+            assert_eq!(count, 1);
+        }
+    }
+
+    // Small enough to run under Miri, e.g. `cargo +nightly miri test static_and_dynamic_aliasing`.
+    #[test]
+    fn static_and_dynamic_aliasing() {
+        let mut nums = (0..17).collect::<Vec<usize>>();
+        let mut sums = Vec::new();
+
+        scope(NonZeroUsize::new(3), |scope| {
+            scope.for_each_static(&mut nums, |nums| {
+                for num in nums {
+                    *num *= 2;
+                }
+            });
+
+            scope.fold_dynamic(&nums, &mut sums, |sum: &mut usize, num| {
+                *sum += num;
+            });
+        });
+
+        assert_eq!(sums.into_iter().map(|sum| sum.0).sum::<usize>(), 17 * 16);
+    }
+
+    #[test]
+    fn iter_static_handles_empty_and_extreme_ranges() {
+        scope(None, |scope| {
+            scope.iter_static(0..0, |_thread, _range| unreachable!());
+            scope.iter_static(usize::MAX..usize::MAX, |_thread, _range| unreachable!());
+
+            let counts = (0..scope.workers() + 1)
+                .map(|_| AtomicUsize::new(0))
+                .collect::<Vec<_>>();
+
+            scope.iter_static(usize::MAX - 3..usize::MAX, |thread, range| {
+                assert!(range.start >= usize::MAX - 3);
+
+                counts[thread].fetch_add(range.len(), Ordering::Relaxed);
+            });
+
+            assert_eq!(
+                counts
+                    .into_iter()
+                    .map(AtomicUsize::into_inner)
+                    .sum::<usize>(),
+                3
+            );
+
+            scope.for_each_static(&mut [] as &mut [usize], |_work| unreachable!());
+
+            let mut sums = Vec::new();
+
+            scope.fold_static(
+                &[] as &[usize],
+                &mut sums,
+                |_sum: &mut usize, _work| unreachable!(),
+            );
+
+            assert!(sums.iter().all(|sum| sum.0 == 0));
+        });
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn u64_iteration_matches_usize_iteration() {
+        let start = u64::from(u32::MAX) - 5;
+        let end = u64::from(u32::MAX) + 7;
+
+        scope(NonZeroUsize::new(4), |scope| {
+            let ranges = Mutex::new(Vec::new());
+
+            scope.iter_static_u64(0..u64::MAX, |thread, range| {
+                ranges.lock().unwrap().push((thread, range));
+            });
+
+            let expected = Mutex::new(Vec::new());
+
+            scope.iter_static(0..usize::MAX, |thread, range| {
+                expected
+                    .lock()
+                    .unwrap()
+                    .push((thread, range.start as u64..range.end as u64));
+            });
+
+            let mut ranges = ranges.into_inner().unwrap();
+            let mut expected = expected.into_inner().unwrap();
+            ranges.sort_by_key(|(thread, _range)| *thread);
+            expected.sort_by_key(|(thread, _range)| *thread);
+
+            assert_eq!(ranges, expected);
+
+            let indices = Mutex::new(Vec::new());
+
+            scope.iter_dynamic_u64(start..end, |_thread, index| {
+                indices.lock().unwrap().push(index);
+            });
+
+            let mut indices = indices.into_inner().unwrap();
+            indices.sort_unstable();
+
+            assert_eq!(indices, (start..end).collect::<Vec<_>>());
+        });
+    }
+
+    #[test]
+    fn iter_static_resumable_reports_progress() {
+        scope(NonZeroUsize::new(4), |scope| {
+            assert_eq!(
+                scope.iter_static_resumable(5..5, |_thread, _range| unreachable!()),
+                [5; 4]
+            );
+
+            let next = scope
+                .iter_static_resumable(0..10, |_thread, range| range.start + range.len().min(2));
+
+            assert_eq!(next, [2, 5, 8, 10]);
+
+            let next = scope.iter_static_resumable(0..2, |_thread, range| range.end);
+
+            assert_eq!(next, [1, 2, 2, 2]);
+        });
+    }
+
+    #[test]
+    fn iter_static_offset_works() {
+        let parallelism = NonZeroUsize::new(4);
+
+        scope(parallelism, |scope| {
+            scope.iter_static_offset(10..20, |thread, offset, range| {
+                assert_eq!(offset, (3 * thread).min(10));
+                assert_eq!(range.start, 10 + offset);
+            });
+        });
+    }
+
+    #[test]
+    fn tiles_2d_cover_grid() {
+        for (rows, cols) in [(0, 5), (5, 0), (1, 1), (7, 3), (100, 17)] {
+            let counts = (0..rows * cols)
+                .map(|_| AtomicUsize::new(0))
+                .collect::<Vec<_>>();
+
+            scope(NonZeroUsize::new(4), |scope| {
+                scope.tiles_2d(rows, cols, |_thread, row_range, col_range| {
+                    for row in row_range {
+                        for col in col_range.clone() {
+                            counts[row * cols + col].fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            });
+
+            for count in counts {
+                assert_eq!(count.into_inner(), 1);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "element 4 was handed to thread 2 and thread 0")]
+    fn claims_detect_overlapping_chunks() {
+        let claims = super::Claims::new(10);
+
+        claims.claim(0, 0..5);
+        claims.claim(1, 5..5);
+        claims.claim(2, 4..10);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "range 5..11 was handed to thread 1 but there are only 10 elements")]
+    fn claims_detect_out_of_bounds_chunks() {
+        let claims = super::Claims::new(10);
+
+        claims.claim(0, 0..5);
+        claims.claim(1, 5..11);
+    }
+
+    #[test]
+    fn for_each_static_with_works() {
+        let mut nums = (0..1_000).collect::<Vec<usize>>();
+
+        let inits = (0..4).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+        let finishes = (0..4).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+
+        scope(NonZeroUsize::new(4), |scope| {
+            scope.for_each_static_with(
+                &mut nums,
+                |thread| {
+                    inits[thread].fetch_add(1, Ordering::Relaxed);
+
+                    (thread, 0)
+                },
+                |(thread, count)| {
+                    assert_eq!(count, 250);
+
+                    finishes[thread].fetch_add(1, Ordering::Relaxed);
+                },
+                |(_thread, count), nums| {
+                    for num in nums {
+                        *num *= 2;
+                        *count += 1;
+                    }
+                },
+            );
+        });
+
+        for (index, num) in nums.into_iter().enumerate() {
+            assert_eq!(num, 2 * index);
+        }
+
+        for count in inits.into_iter().chain(finishes) {
+            assert_eq!(count.into_inner(), 1);
+        }
+    }
+
+    #[test]
+    fn for_each_static_aligned_works() {
+        let parallelism = NonZeroUsize::new(4);
+
+        let mut data = vec![0_u8; 1_000];
+        let data = &mut data[3..];
+        let base = data.as_ptr().addr();
+
+        let chunks = Mutex::new(Vec::new());
+
+        scope(parallelism, |scope| {
+            scope.for_each_static_aligned(data, 64, |data| {
+                for byte in &mut *data {
+                    *byte += 1;
+                }
+
+                chunks
+                    .lock()
+                    .unwrap()
+                    .push((data.as_ptr().addr() - base, data.len()));
+            });
+        });
+
+        let mut chunks = chunks.into_inner().unwrap();
+        chunks.sort_unstable();
+
+        let mut end = 0;
+
+        for (start, len) in chunks {
+            assert_eq!(start, end);
+
+            if start != 0 && len != 0 {
+                assert_eq!((base + start) % 64, 0);
+            }
+
+            end += len;
+        }
+
+        assert_eq!(end, data.len());
+        assert!(data.iter().all(|byte| *byte == 1));
+    }
+
+    #[test]
+    fn chunks_static_works() {
+        let nums = (0..1_000).collect::<Vec<usize>>();
+
+        let chunks = Mutex::new(Vec::new());
+
+        scope(NonZeroUsize::new(4), |scope| {
+            scope.chunks_static(&nums, |thread, chunk| {
+                chunks.lock().unwrap().push((thread, chunk.to_vec()));
+            });
+        });
+
+        let mut chunks = chunks.into_inner().unwrap();
+        chunks.sort_unstable();
+
+        assert_eq!(
+            chunks
+                .into_iter()
+                .flat_map(|(_thread, chunk)| chunk)
+                .collect::<Vec<_>>(),
+            nums
+        );
+    }
+
+    #[test]
+    fn for_each_static_halo_clamps_at_boundaries() {
+        let nums = (0..1_000).collect::<Vec<usize>>();
+
+        let views = Mutex::new(Vec::new());
+
+        scope(NonZeroUsize::new(4), |scope| {
+            scope.for_each_static_halo(&nums, 3, |range, view| {
+                views
+                    .lock()
+                    .unwrap()
+                    .push((range, view.first().copied(), view.len()));
+            });
+        });
+
+        let mut views = views.into_inner().unwrap();
+        views.sort_unstable_by_key(|(range, _first, _len)| range.start);
+
+        assert_eq!(
+            views,
+            [
+                (0..250, Some(0), 253),
+                (250..500, Some(247), 256),
+                (500..750, Some(497), 256),
+                (750..1_000, Some(747), 253),
+            ]
+        );
+    }
+
+    #[test]
+    fn chunks_exact_static_rounds_to_lanes() {
+        let mut nums = (0..1_003).collect::<Vec<usize>>();
+
+        let lens = Mutex::new(Vec::new());
+        let mut tail_len = None;
+
+        scope(NonZeroUsize::new(4), |scope| {
+            scope.chunks_exact_static(
+                &mut nums,
+                8,
+                |chunk| {
+                    lens.lock().unwrap().push(chunk.len());
+
+                    for num in chunk {
+                        *num += 1;
+                    }
+                },
+                |tail| {
+                    tail_len = Some(tail.len());
+
+                    for num in tail {
+                        *num += 2;
+                    }
+                },
+            );
+        });
+
+        let lens = lens.into_inner().unwrap();
+
+        assert!(lens.iter().all(|len| len % 8 == 0));
+        assert_eq!(lens.iter().sum::<usize>(), 1_000);
+        assert_eq!(tail_len, Some(3));
+
+        for (index, num) in nums.into_iter().enumerate() {
+            assert_eq!(num, index + if index < 1_000 { 1 } else { 2 });
+        }
+    }
+
+    #[test]
+    fn map_chunks_static_works() {
+        let bytes = (0..1_000).map(|num| num as u8).collect::<Vec<_>>();
+
+        let checksum = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .fold(0_u32, |sum, byte| sum.rotate_left(5) ^ u32::from(*byte))
+        };
+
+        for parallelism in [1, 3, 4] {
+            let checksums = scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.map_chunks_static(&bytes, |_thread, bytes| checksum(bytes))
+            });
+
+            let expected = bytes
+                .chunks(bytes.len().div_ceil(parallelism))
+                .map(checksum)
+                .collect::<Vec<_>>();
+
+            assert_eq!(checksums.len(), parallelism);
+            assert_eq!(checksums, expected);
+
+            let lens = scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.map_chunks_static(&bytes[..0], |_thread, bytes| bytes.len())
+            });
+
+            assert_eq!(lens, vec![0; parallelism]);
+        }
+    }
+
+    #[test]
+    fn for_each_static_indexed_works() {
+        let length = 1_000;
+
+        let mut nums = vec![0; length];
+
+        scope(None, |scope| {
+            scope.for_each_static_indexed(&mut nums, |range, nums| {
+                assert_eq!(range.len(), nums.len());
+
+                for (index, num) in range.zip(nums) {
+                    *num = index;
+                }
+            });
+        });
+
+        assert!(
+            nums.into_iter()
+                .enumerate()
+                .all(|(index, num)| index == num)
+        );
+    }
+
+    #[test]
+    fn for_each_rows_respects_row_boundaries() {
+        let mut rows = (0..50)
+            .map(|row| vec![0; [0, 1, 100][row % 3]])
+            .collect::<Vec<Vec<usize>>>();
+
+        scope(NonZeroUsize::new(4), |scope| {
+            scope.for_each_rows(&mut rows, |index, row| {
+                for (col, value) in row.iter_mut().enumerate() {
+                    *value = index * 1_000 + col;
+                }
+            });
+        });
+
+        for (index, row) in rows.into_iter().enumerate() {
+            assert_eq!(row.len(), [0, 1, 100][index % 3]);
+
+            for (col, value) in row.into_iter().enumerate() {
+                assert_eq!(value, index * 1_000 + col);
+            }
+        }
+    }
+
+    #[test]
+    fn for_each_zip_works() {
+        let length = 1_000;
+
+        let mut positions = vec![0.0; length];
+        let mut velocities = (0..length).map(|index| index as f64).collect::<Vec<_>>();
+
+        scope(None, |scope| {
+            scope.for_each_static_zip(&mut positions, &mut velocities, |positions, velocities| {
+                for (position, velocity) in positions.iter_mut().zip(velocities) {
+                    *position += *velocity;
+                    *velocity *= 2.0;
+                }
+            });
+
+            scope.for_each_dynamic_zip(&mut positions, &mut velocities, |position, velocity| {
+                *position += *velocity;
+            });
+        });
+
+        for (index, position) in positions.into_iter().enumerate() {
+            assert_eq!(position, 3.0 * index as f64);
+        }
+    }
+
+    #[test]
+    fn for_each_static_zip3_and_zip4_work() {
+        let length = 1_000;
+
+        let mut xs = (0..length).map(|index| index as f64).collect::<Vec<_>>();
+        let mut ys = vec![1.0; length];
+        let mut zs = vec![0.0; length];
+        let mut masses = vec![2.0; length];
+
+        scope(NonZeroUsize::new(3), |scope| {
+            scope.for_each_static_zip3(&mut xs, &mut ys, &mut zs, |xs, ys, zs| {
+                for ((x, y), z) in xs.iter().zip(ys).zip(zs) {
+                    *z = 3.0 * x + *y;
+                }
+            });
+
+            scope.for_each_static_zip4(
+                &mut xs,
+                &mut ys,
+                &mut zs,
+                &mut masses,
+                |xs, ys, zs, masses| {
+                    for (((x, y), z), mass) in xs.iter_mut().zip(ys).zip(zs).zip(masses) {
+                        *x = *z / *mass;
+                        *y = 0.0;
+                    }
+                },
+            );
+        });
+
+        for (index, (x, y)) in xs.into_iter().zip(ys).enumerate() {
+            assert_eq!(x, (3.0 * index as f64 + 1.0) / 2.0);
+            assert_eq!(y, 0.0);
+        }
+    }
+
+    #[test]
+    fn for_each_static_zip3_and_zip4_reject_mismatched_lengths() {
+        scope(None, |scope| {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                scope.for_each_static_zip3(
+                    &mut [1],
+                    &mut [2],
+                    &mut [3, 4],
+                    |_a, _b, _c| unreachable!(),
+                );
+            }));
+
+            assert!(result.is_err());
+
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                scope.for_each_static_zip4(
+                    &mut [1],
+                    &mut [2],
+                    &mut [3],
+                    &mut [] as &mut [i32],
+                    |_a, _b, _c, _d| unreachable!(),
+                );
+            }));
+
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn for_each_zip_rejects_mismatched_lengths() {
+        let called = AtomicBool::new(false);
+
+        scope(None, |scope| {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                scope.for_each_static_zip(&mut [1, 2, 3], &mut [4, 5], |_a, _b| {
+                    called.store(true, Ordering::Relaxed);
+                });
+            }));
+
+            assert!(result.is_err());
+
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                scope.for_each_dynamic_zip(&mut [1, 2], &mut [3, 4, 5], |_a, _b| {
+                    called.store(true, Ordering::Relaxed);
+                });
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(!called.into_inner());
+    }
+
+    #[test]
+    fn fold_static_works() {
+        let length = 1_000;
+
+        let nums = (0..length).collect::<Vec<_>>();
+        let mut sums = Vec::new();
+
+        scope(None, |scope| {
+            scope.fold_static(&nums, &mut sums, |sum: &mut usize, nums| {
+                for num in nums {
+                    *sum += num;
+                }
+            });
+        });
+
+        let sum: usize = sums.into_iter().map(|sum| sum.0).sum();
+
+        assert_eq!(sum, length * (length - 1) / 2);
+    }
+
+    #[test]
+    fn fold_static_into_keeps_accumulators() {
+        let length = 1_000;
+
+        let nums = (0..length).collect::<Vec<_>>();
+
+        scope(None, |scope| {
+            let mut sums = vec![Aligned(0); scope.parallelism()];
+
+            for _ in 0..3 {
+                scope.fold_static_into(&nums, &mut sums, |sum, nums| {
+                    for num in nums {
+                        *sum += num;
+                    }
+                });
+            }
+
+            let sum: usize = sums.into_iter().map(|sum| sum.0).sum();
+
+            assert_eq!(sum, 3 * length * (length - 1) / 2);
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn fold_static_into_rejects_wrong_length() {
+        scope(None, |scope| {
+            let mut sums = vec![Aligned(0); scope.parallelism() + 1];
+
+            scope.fold_static_into(&[1, 2, 3], &mut sums, |_sum, _nums| ());
+        });
+    }
+
+    #[test]
+    fn fold_static_drops_accumulators_once_after_panic() {
+        static CREATED: AtomicUsize = AtomicUsize::new(0);
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counted(Vec<usize>);
+
+        impl Default for Counted {
+            fn default() -> Self {
+                CREATED.fetch_add(1, Ordering::Relaxed);
+
+                Self(Vec::new())
+            }
+        }
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPPED.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let nums = (0..1_000).collect::<Vec<usize>>();
+
+        let panic = catch_unwind(AssertUnwindSafe(|| {
+            scope(NonZeroUsize::new(4), |scope| {
+                let mut accum = Vec::new();
+
+                scope.fold_static(&nums, &mut accum, |accum: &mut Counted, nums| {
+                    for &num in nums {
+                        if num == 600 {
+                            panic!("fold panicked");
+                        }
+
+                        accum.0.push(num);
+                    }
+                });
+            });
+        }))
+        .unwrap_err();
+
+        assert_eq!(*panic.downcast::<&str>().unwrap(), "fold panicked");
+
+        assert_eq!(CREATED.load(Ordering::Relaxed), 4);
+        assert_eq!(DROPPED.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn fold_static_reuse_resets_accumulators() {
+        let length = 1_000;
+
+        let nums = (0..length).collect::<Vec<_>>();
+
+        scope(None, |scope| {
+            let mut sums = vec![Aligned(0); scope.parallelism()];
+            let sums_ptr = sums.as_ptr();
+
+            for _ in 0..3 {
+                scope.fold_static_reuse(&nums, &mut sums, |sum, nums| {
+                    for num in nums {
+                        *sum += num;
+                    }
+                });
+
+                let sum: usize = sums.iter().map(|sum| sum.0).sum();
+
+                assert_eq!(sum, length * (length - 1) / 2);
+            }
+
+            assert_eq!(sums.as_ptr(), sums_ptr);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "accumulators must be sized for the parallelism of the scope")]
+    fn fold_static_reuse_rejects_wrong_length() {
+        scope(None, |scope| {
+            let mut sums = vec![Aligned(0); scope.parallelism() + 1];
+
+            scope.fold_static_reuse(&[1, 2, 3], &mut sums, |_sum, _nums| ());
+        });
+    }
+
+    #[test]
+    fn try_fold_static_works() {
+        let strings = (0..1_000).map(|num| num.to_string()).collect::<Vec<_>>();
+
+        scope(None, |scope| {
+            let sums = scope
+                .try_fold_static(&strings, |sum: &mut usize, string| {
+                    *sum += string.parse::<usize>()?;
+                    Ok::<_, std::num::ParseIntError>(())
+                })
+                .unwrap();
+
+            let sum: usize = sums.into_iter().map(|sum| sum.0).sum();
+
+            assert_eq!(sum, 1_000 * 999 / 2);
+        });
+
+        let mut strings = strings;
+        strings[500] = "not a number".to_owned();
+
+        scope(None, |scope| {
+            let result = scope.try_fold_static(&strings, |sum: &mut usize, string| {
+                *sum += string.parse::<usize>()?;
+                Ok::<_, std::num::ParseIntError>(())
+            });
+
+            assert!(result.is_err());
+        });
+
+        strings[500] = "500".to_owned();
+        strings[0] = "not a number".to_owned();
+
+        let errored = AtomicBool::new(false);
+        let processed = AtomicUsize::new(0);
+
+        scope(NonZeroUsize::new(2), |scope| {
+            let result = scope.try_fold_static(&strings, |sum: &mut usize, string| {
+                processed.fetch_add(1, Ordering::Relaxed);
+
+                let num = string.parse::<usize>().inspect_err(|_err| {
+                    errored.store(true, Ordering::Relaxed);
+                })?;
+
+                // Make the other thread start only after the first element of thread 0 failed.
+                while !errored.load(Ordering::Relaxed) {
+                    thread::yield_now();
+                }
+                thread::sleep(Duration::from_millis(1));
+
+                *sum += num;
+                Ok::<_, std::num::ParseIntError>(())
+            });
+
+            assert!(result.is_err());
+        });
+
+        // Without stopping early, the other thread would process all of its 500 elements.
+        assert!(processed.into_inner() < 100);
+    }
+
+    #[test]
+    fn map_static_works() {
+        let length = 1_000;
+
+        let nums = (0..length).collect::<Vec<_>>();
+        let mut squares = vec![0; length];
+
+        scope(None, |scope| {
+            scope.map_static(&nums, &mut squares, |nums, squares| {
+                for (num, square) in nums.iter().zip(squares) {
+                    *square = num * num;
+                }
+            });
+        });
+
+        for (num, square) in squares.into_iter().enumerate() {
+            assert_eq!(square, num * num);
+        }
+    }
+
+    #[test]
+    fn map_static_handles_empty_and_uneven_work() {
+        let parallelism = NonZeroUsize::new(4);
+
+        for length in [0, 1, 5, 7] {
+            let nums = (0..length).collect::<Vec<_>>();
+            let mut doubles = vec![0; length];
+
+            scope(parallelism, |scope| {
+                scope.map_static(&nums, &mut doubles, |nums, doubles| {
+                    assert_eq!(nums.len(), doubles.len());
+
+                    for (num, double) in nums.iter().zip(doubles) {
+                        *double = 2 * num;
+                    }
+                });
+            });
+
+            for (num, double) in doubles.into_iter().enumerate() {
+                assert_eq!(double, 2 * num);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_static_rejects_mismatched_lengths() {
+        scope(None, |scope| {
+            scope.map_static(&[1, 2, 3], &mut [0; 2], |_nums, _doubles| ());
+        });
+    }
+
+    #[test]
+    fn iter_dynamic_stays_within_offset_range() {
+        let parallelism = NonZeroUsize::new(9);
+
+        let counts = (0..10).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+
+        scope(parallelism, |scope| {
+            scope.iter_dynamic(5..7, |_thread, index| {
+                counts[index].fetch_add(1, Ordering::Relaxed);
+            });
+        });
+
+        for (index, count) in counts.into_iter().enumerate() {
+            assert_eq!(count.into_inner(), (5..7).contains(&index) as usize);
+        }
+    }
+
+    #[test]
+    fn dynamic_load_sums_to_total_work() {
+        scope(NonZeroUsize::new(4), |scope| {
+            scope.iter_dynamic(0..10, |_thread, _index| ());
+
+            assert!(scope.dynamic_load().is_none());
+        });
+
+        let config = Config {
+            parallelism: NonZeroUsize::new(4),
+            track_load: true,
+            ..Default::default()
+        };
+
+        scope_with(config, |scope| {
+            assert!(scope.dynamic_load().is_none());
+
+            for len in [1_000, 3] {
+                scope.iter_dynamic(0..len, |_thread, _index| ());
+
+                let load = scope.dynamic_load().unwrap();
+
+                assert_eq!(load.total, len);
+                assert!(load.min <= load.max);
+                assert_eq!(load.avg, len as f64 / 4.0);
+            }
+
+            scope
+                .with_active_workers(1)
+                .iter_dynamic(0..10, |_thread, _index| ());
+
+            let load = scope.dynamic_load().unwrap();
+
+            assert_eq!(load.total, 10);
+            assert_eq!(load.avg, 5.0);
+
+            let mut sums = Vec::new();
+
+            scope.fold_dynamic(&[1; 100], &mut sums, |sum: &mut usize, num| *sum += num);
+
+            assert_eq!(scope.dynamic_load().unwrap().total, 100);
+        });
+    }
+
+    #[cfg(feature = "prefetch")]
+    #[test]
+    fn iter_dynamic_prefetch_processes_each_index_once() {
+        for parallelism in [1, 4] {
+            let data = (0..1_000).collect::<Vec<usize>>();
+            let counts = (0..1_010).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+
+            scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.iter_dynamic_prefetch(
+                    10..1_010,
+                    |index| &data[index - 10],
+                    |_thread, index| {
+                        counts[index].fetch_add(1, Ordering::Relaxed);
+                    },
+                );
+            });
+
+            for (index, count) in counts.into_iter().enumerate() {
+                assert_eq!(count.into_inner(), (index >= 10) as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn iter_dynamic_until_stops_early() {
+        let processed = AtomicUsize::new(0);
+
+        scope(NonZeroUsize::new(1), |scope| {
+            scope.iter_dynamic_until(0..1_000, |_thread, index| {
+                processed.fetch_add(1, Ordering::Relaxed);
+
+                if index == 100 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
+        });
+
+        assert_eq!(processed.into_inner(), 101);
+    }
+
+    #[test]
+    fn find_first_returns_lowest_index() {
+        let length = 1_000;
+
+        let mut nums = vec![0; length];
+
+        for index in [0, 1, 499, 500, 998, 999] {
+            nums[index] = 1;
+        }
+
+        let indices = (0..length).collect::<Vec<_>>();
+
+        scope(None, |scope| {
+            assert_eq!(scope.find_first(&nums, |num| *num == 1), Some(0));
+            assert_eq!(scope.find_first(&nums[1..], |num| *num == 1), Some(0));
+            assert_eq!(scope.find_first(&nums[2..], |num| *num == 1), Some(497));
+            assert_eq!(scope.find_first(&nums[501..], |num| *num == 1), Some(497));
+            assert_eq!(scope.find_first(&nums[999..], |num| *num == 1), Some(0));
+            assert_eq!(scope.find_first(&indices, |index| *index == 999), Some(999));
+            assert_eq!(scope.find_first(&nums, |num| *num == 2), None);
+            assert_eq!(scope.find_first(&[] as &[usize], |num| *num == 1), None);
+        });
+    }
+
+    #[test]
+    fn iter_dynamic_chunked_works() {
+        for (length, chunk) in [(0, 1), (1_000, 1), (1_000, 7), (1_000, 1_000), (10, 100)] {
+            let counts = (0..length).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+
+            scope(None, |scope| {
+                scope.iter_dynamic_chunked(
+                    0..length,
+                    NonZeroUsize::new(chunk).unwrap(),
+                    |_thread, range| {
+                        assert!(range.len() <= chunk);
+
+                        for index in range {
+                            counts[index].fetch_add(1, Ordering::Relaxed);
+                        }
+                    },
+                );
+            });
+
+            for count in counts {
+                assert_eq!(count.into_inner(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn iter_adaptive_works() {
+        for length in [0, 1, 2, 10, 1_000] {
+            let counts = (0..length).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+
+            scope(NonZeroUsize::new(4), |scope| {
+                scope.iter_adaptive(0..length, |_thread, index| {
+                    counts[index].fetch_add(1, Ordering::Relaxed);
+                });
+            });
+
+            for count in counts {
+                assert_eq!(count.into_inner(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn iter_guided_works() {
+        for length in [0, 1, 10, 1_000] {
+            let counts = (0..length).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+            let max_chunk = AtomicUsize::new(0);
+
+            scope(NonZeroUsize::new(4), |scope| {
+                scope.iter_guided(0..length, |_thread, range| {
+                    max_chunk.fetch_max(range.len(), Ordering::Relaxed);
+
+                    for index in range {
+                        counts[index].fetch_add(1, Ordering::Relaxed);
+                    }
+                });
+            });
+
+            assert!(max_chunk.into_inner() <= length.div_ceil(4).max(1));
+
+            for count in counts {
+                assert_eq!(count.into_inner(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn iter_dynamic_tapered_processes_each_index_once() {
+        for parallelism in [1, 3, 8] {
+            let counts = (0..1_010).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+            let chunks = Mutex::new(vec![Vec::new(); parallelism]);
+
+            scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.iter_dynamic_tapered(10..1_010, |thread, range| {
+                    chunks.lock().unwrap()[thread].push(range.len());
+
+                    for index in range {
+                        counts[index].fetch_add(1, Ordering::Relaxed);
+                    }
+                });
+            });
+
+            for (index, count) in counts.into_iter().enumerate() {
+                assert_eq!(count.into_inner(), (index >= 10) as usize);
+            }
+
+            for chunks in chunks.into_inner().unwrap() {
+                assert!(chunks.windows(2).all(|pair| pair[0] >= pair[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn iter_stealing_processes_each_index_once() {
+        for length in [0, 1, 10, 1_000] {
+            let counts = (0..length).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+            let stolen = AtomicBool::new(false);
+
+            scope(NonZeroUsize::new(4), |scope| {
+                scope.iter_stealing(0..length, |thread, index| {
+                    // Thread 0 blocks on its first index until its range has been stolen from.
+                    if thread == 0 && index == 0 && length.div_ceil(4) > 1 {
+                        while !stolen.load(Ordering::Acquire) {
+                            std::thread::yield_now();
+                        }
+                    } else if thread != 0 && index < length.div_ceil(4) {
+                        stolen.store(true, Ordering::Release);
+                    }
+
+                    counts[index].fetch_add(1, Ordering::Relaxed);
+                });
+            });
+
+            for count in counts {
+                assert_eq!(count.into_inner(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn for_each_dynamic_works() {
+        let length = 1_000;
+
+        let mut counts = (0..length).map(|_| 0).collect::<Vec<_>>();
+
+        scope(None, |scope| {
+            scope.for_each_dynamic(&mut counts, |count| {
+                *count += 1;
+            });
+        });
+
+        for count in counts {
+            assert_eq!(count, 1);
+        }
+    }
+
+    #[test]
+    fn for_each_dynamic_indexed_works() {
+        let length = 1_000;
+
+        let mut nums = vec![0; length];
+
+        scope(None, |scope| {
+            scope.for_each_dynamic_indexed(&mut nums, |index, num| *num = index);
+        });
+
+        assert!(
+            nums.into_iter()
+                .enumerate()
+                .all(|(index, num)| index == num)
+        );
+    }
+
+    #[test]
+    fn try_for_each_dynamic_works() {
+        let length = 1_000;
+
+        let mut counts = (0..length).map(|_| 0).collect::<Vec<_>>();
+
+        let result = scope(None, |scope| {
+            scope.try_for_each_dynamic(&mut counts, |count| {
+                *count += 1;
+
+                Ok::<_, ()>(())
+            })
+        });
+
+        assert_eq!(result, Ok(()));
+
+        for count in counts {
+            assert_eq!(count, 1);
+        }
+    }
+
+    #[test]
+    fn try_for_each_dynamic_skips_remaining_work() {
+        let length = 1_000;
+
+        let mut nums = (0..length).collect::<Vec<_>>();
+        let processed = AtomicUsize::new(0);
+
+        let result = scope(NonZeroUsize::new(1), |scope| {
+            scope.try_for_each_dynamic(&mut nums, |num| {
+                processed.fetch_add(1, Ordering::Relaxed);
+
+                if *num == 10 { Err(*num) } else { Ok(()) }
+            })
+        });
+
+        assert_eq!(result, Err(10));
+        assert_eq!(processed.into_inner(), 11);
+
+        let result = scope(NonZeroUsize::new(4), |scope| {
+            scope.try_for_each_dynamic(&mut nums, |num| if *num == 10 { Err(*num) } else { Ok(()) })
+        });
+
+        assert_eq!(result, Err(10));
+    }
+
+    #[test]
+    fn for_each_dynamic_with_works() {
+        let length = 1_000;
+
+        let mut nums = (0..length).collect::<Vec<_>>();
+
+        let locals = scope(None, |scope| {
+            scope.for_each_dynamic_with(
+                &mut nums,
+                || (Vec::new(), 0),
+                |(scratch, count), num| {
+                    scratch.clear();
+                    scratch.extend((0..*num % 10).rev());
+                    scratch.sort_unstable();
+
+                    *num = scratch.len();
+                    *count += 1;
+                },
+            )
+        });
+
+        for (index, num) in nums.into_iter().enumerate() {
+            assert_eq!(num, index % 10);
+        }
+
+        let count: usize = locals.into_iter().map(|local| local.0.1).sum();
+
+        assert_eq!(count, length);
+    }
+
+    #[test]
+    fn map_dynamic_works() {
+        let length = 1_000;
+
+        let nums = (0..length).collect::<Vec<_>>();
+        let mut strings = vec![String::new(); length];
+
+        scope(None, |scope| {
+            scope.map_dynamic(&nums, &mut strings, |num| num.to_string());
+        });
+
+        for (num, string) in strings.into_iter().enumerate() {
+            assert_eq!(string, num.to_string());
+        }
+    }
+
+    #[test]
+    fn map_dynamic_skips_empty_work() {
+        scope(None, |scope| {
+            scope.map_dynamic(
+                &[] as &[usize],
+                &mut [] as &mut [usize],
+                |_num| unreachable!(),
+            );
+        });
     }
 
     #[test]
     fn fold_dynamic_works() {
         let length = 1_000;
 
-        let nums = (0..length).map(|index| index).collect::<Vec<_>>();
+        let nums = (0..length).collect::<Vec<_>>();
         let mut sums = Vec::new();
 
         scope(None, |scope| {
@@ -0,0 +1,358 @@
+use std::ops::Range;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::scope::Scope;
+
+impl Scope<'_> {
+    /// Runs `a` on the calling thread while `b` runs on the first worker.
+    ///
+    /// If the scope has no workers, both closures are run sequentially on the calling thread.
+    pub fn join<A, B, RA, RB>(&self, a: A, b: B) -> (RA, RB)
+    where
+        A: FnOnce() -> RA + Send,
+        B: FnOnce() -> RB + Send,
+        RA: Send,
+        RB: Send,
+    {
+        if self.workers == 0 {
+            return (a(), b());
+        }
+
+        let a = Mutex::new(Some(a));
+        let b = Mutex::new(Some(b));
+
+        let ra = Mutex::new(None);
+        let rb = Mutex::new(None);
+
+        self.broadcast(|thread| match thread {
+            0 => {
+                let a = a.lock().unwrap().take().unwrap();
+                *ra.lock().unwrap() = Some(a());
+            }
+            1 => {
+                let b = b.lock().unwrap().take().unwrap();
+                *rb.lock().unwrap() = Some(b());
+            }
+            _ => (),
+        });
+
+        (
+            ra.into_inner().unwrap().unwrap(),
+            rb.into_inner().unwrap().unwrap(),
+        )
+    }
+
+    /// Runs all `tasks` concurrently, handing them out one at a time to whichever thread is idle.
+    ///
+    /// The results are returned in the order of `tasks`. Unlike the iterators, all tasks are run
+    /// even if the scope is cancelled. If the scope has no workers, they are run sequentially.
+    pub fn join_all<T, F>(&self, tasks: Vec<F>) -> Vec<T>
+    where
+        F: FnOnce() -> T + Send,
+        T: Send,
+    {
+        if self.workers == 0 {
+            return tasks.into_iter().map(|task| task()).collect();
+        }
+
+        let tasks = tasks
+            .into_iter()
+            .map(|task| Mutex::new(Some(task)))
+            .collect::<Vec<_>>();
+
+        let results = (0..tasks.len())
+            .map(|_| Mutex::new(None))
+            .collect::<Vec<_>>();
+
+        let next_index = AtomicUsize::new(self.workers + 1);
+
+        self.broadcast(|thread| {
+            let mut index = thread;
+
+            while index < tasks.len() {
+                let task = tasks[index].lock().unwrap().take().unwrap();
+                *results[index].lock().unwrap() = Some(task());
+
+                index = next_index.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        results
+            .into_iter()
+            .map(|result| result.into_inner().unwrap().unwrap())
+            .collect()
+    }
+
+    /// Runs `a` and `b` concurrently like [`Scope::join`], passing each a scope of its own.
+    ///
+    /// The threads of this scope are split into two groups, the first one led by the calling thread
+    /// and the second one by the first thread not part of the first group. Each sub-scope broadcasts
+    /// only to the workers of its group and has its own barrier and cancellation state.
+    /// If the scope has no workers, both closures are run sequentially on the calling thread.
+    pub fn fork<A, B, RA, RB>(&self, a: A, b: B) -> (RA, RB)
+    where
+        A: for<'fork> FnOnce(Scope<'fork>) -> RA + Send,
+        B: for<'fork> FnOnce(Scope<'fork>) -> RB + Send,
+        RA: Send,
+        RB: Send,
+    {
+        if self.workers == 0 {
+            let group = self.state.group(0);
+
+            return (group.lead_group(a), group.lead_group(b));
+        }
+
+        let a_workers = self.workers / 2;
+        let b_leader = a_workers + 1;
+
+        let a_group = self.state.group(a_workers);
+        let b_group = self.state.group(self.workers - b_leader);
+
+        let a = Mutex::new(Some(a));
+        let b = Mutex::new(Some(b));
+
+        let ra = Mutex::new(None);
+        let rb = Mutex::new(None);
+
+        self.broadcast(|thread| {
+            if thread == 0 {
+                let a = a.lock().unwrap().take().unwrap();
+                *ra.lock().unwrap() = Some(a_group.lead_group(a));
+            } else if thread < b_leader {
+                a_group.join_group(thread);
+            } else if thread == b_leader {
+                let b = b.lock().unwrap().take().unwrap();
+                *rb.lock().unwrap() = Some(b_group.lead_group(b));
+            } else {
+                b_group.join_group(thread - b_leader);
+            }
+        });
+
+        (
+            ra.into_inner().unwrap().unwrap(),
+            rb.into_inner().unwrap().unwrap(),
+        )
+    }
+
+    /// Recursively halves `work` until each piece is no longer than `min_len`
+    /// and dispatches the resulting pieces dynamically across all threads.
+    ///
+    /// Each piece is derived from its index in the recursion tree when a thread claims it,
+    /// so no pieces are materialized up front and at most one piece per thread is in flight.
+    ///
+    /// If the scope has no workers, `f` is called once for the whole of `work`.
+    pub fn split_recursive<F>(&self, work: Range<usize>, min_len: usize, f: F)
+    where
+        F: Fn(Range<usize>) + Sync,
+    {
+        if work.is_empty() {
+            return;
+        }
+
+        if self.workers == 0 {
+            f(work);
+            return;
+        }
+
+        // Halving `depth` times makes every piece no longer than `min_len`.
+        let pieces = work.len().div_ceil(min_len.max(1)).next_power_of_two();
+        let depth = pieces.trailing_zeros();
+
+        self.uncancellable()
+            .iter_dynamic(0..pieces, |_thread, index| {
+                let mut piece = work.clone();
+
+                // Descends the recursion tree choosing the lower or upper half by the bits of `index`.
+                for level in (0..depth).rev() {
+                    let mid = piece.start + piece.len() / 2;
+
+                    if index >> level & 1 == 0 {
+                        piece.end = mid;
+                    } else {
+                        piece.start = mid;
+                    }
+                }
+
+                // Pieces of the last level can be empty if `min_len` does not divide `work` evenly.
+                if !piece.is_empty() {
+                    f(piece);
+                }
+            });
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::scope::scope;
+
+    #[test]
+    fn join_works() {
+        for parallelism in [1, 2, 4] {
+            let caller = thread::current().id();
+
+            let ((a, a_thread), (b, b_thread)) = scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.join(
+                    || (1 + 2, thread::current().id()),
+                    || ("3".repeat(2), thread::current().id()),
+                )
+            });
+
+            assert_eq!(a, 3);
+            assert_eq!(b, "33");
+
+            assert_eq!(a_thread, caller);
+            assert_eq!(b_thread == caller, parallelism == 1);
+        }
+    }
+
+    #[test]
+    fn join_all_preserves_task_order() {
+        for parallelism in [1, 2, 4] {
+            for len in [0, 2, 100] {
+                let tasks = (0..len)
+                    .map(|index| {
+                        move || {
+                            thread::sleep(Duration::from_micros(index as u64 % 7 * 10));
+
+                            index
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let results = scope(NonZeroUsize::new(parallelism), |scope| {
+                    scope.join_all(tasks)
+                });
+
+                assert_eq!(results, (0..len).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn fork_passes_independent_scopes() {
+        for parallelism in [1, 2, 4] {
+            let nums = (0..1_000).collect::<Vec<usize>>();
+
+            let (a, b) = scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.fork(
+                    |scope| {
+                        let mut sums = Vec::new();
+
+                        scope.fold_static(&nums, &mut sums, |sum: &mut usize, nums| {
+                            *sum += nums.iter().sum::<usize>();
+                        });
+
+                        (scope.parallelism(), sums.into_iter().map(|sum| sum.0).sum())
+                    },
+                    |scope| {
+                        let scope = scope.with_active_workers(3);
+
+                        (scope.parallelism(), scope.count(&nums, |num| num % 2 == 0))
+                    },
+                )
+            });
+
+            let expected = match parallelism {
+                1 | 2 => (1, 1),
+                _ => (2, 2),
+            };
+
+            assert_eq!(a, (expected.0, 1_000 * 999 / 2));
+            assert_eq!(b, (expected.1, 500));
+        }
+    }
+
+    #[test]
+    fn fork_runs_groups_with_independent_barriers() {
+        for parallelism in [1, 3, 5, 8] {
+            let (a, b) = scope(NonZeroUsize::new(parallelism), |scope| {
+                let run = |scope: crate::Scope<'_>| {
+                    let barrier = scope.barrier();
+
+                    (0..100)
+                        .map(|_| {
+                            scope
+                                .broadcast_with(|thread| {
+                                    barrier.wait();
+
+                                    thread
+                                })
+                                .len()
+                        })
+                        .sum::<usize>()
+                };
+
+                scope.fork(run, run)
+            });
+
+            let a_threads = parallelism.div_ceil(2);
+
+            assert_eq!(a, 100 * a_threads);
+            assert_eq!(b, 100 * (parallelism - a_threads).max(1));
+        }
+    }
+
+    #[test]
+    fn split_recursive_works() {
+        for parallelism in [1, 4] {
+            let counts = (0..1_000).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+            let calls = AtomicUsize::new(0);
+
+            scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.split_recursive(0..counts.len(), 10, |range| {
+                    if parallelism != 1 {
+                        assert!(range.len() <= 10);
+                    }
+
+                    calls.fetch_add(1, Ordering::Relaxed);
+
+                    for index in range {
+                        counts[index].fetch_add(1, Ordering::Relaxed);
+                    }
+                });
+            });
+
+            if parallelism == 1 {
+                assert_eq!(calls.into_inner(), 1);
+            } else {
+                assert_eq!(calls.into_inner(), 128);
+            }
+
+            for count in counts {
+                assert_eq!(count.into_inner(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn split_recursive_covers_uneven_ranges() {
+        scope(NonZeroUsize::new(4), |scope| {
+            for len in [1, 3, 7, 1_001] {
+                for min_len in [0, 1, 3] {
+                    let counts = (0..len + 5)
+                        .map(|_| AtomicUsize::new(0))
+                        .collect::<Vec<_>>();
+
+                    scope.split_recursive(5..len + 5, min_len, |range| {
+                        assert!(!range.is_empty());
+                        assert!(range.len() <= min_len.max(1));
+
+                        for index in range {
+                            counts[index].fetch_add(1, Ordering::Relaxed);
+                        }
+                    });
+
+                    for (index, count) in counts.into_iter().enumerate() {
+                        assert_eq!(count.into_inner(), usize::from(index >= 5));
+                    }
+                }
+            }
+        });
+    }
+}
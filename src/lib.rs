@@ -1,31 +1,107 @@
+//! Scoped fork-join parallelism based on broadcasting closures to a fixed set of worker threads.
+//!
+//! The default `std` feature provides the entry points which spawn these threads using [`std::thread`],
+//! i.e. `scope`, `scope_with` and `Pool::new`. Without it, the workers can still be run on threads
+//! spawned by the caller using [`Pool::with_spawner`]. The crate itself requires `std` in either case
+//! as its broadcast protocol relies on parking threads and locking mutexes.
+#![allow(clippy::redundant_locals)]
+
+mod affinity;
+mod barrier;
+mod builder;
+mod cancel;
+mod config;
 mod iter;
+mod join;
+mod pool;
+#[cfg(feature = "queue")]
+mod queue;
+mod reduce;
+#[cfg(feature = "rand_core")]
+mod rng;
 mod scope;
+mod slice;
+mod sort;
+mod sync;
+mod task;
 
 use std::ops::{Deref, DerefMut};
 
-pub use scope::{Scope, scope};
+pub use barrier::Barrier;
+pub use builder::Builder;
+pub use cancel::CancelToken;
+pub use config::Config;
+pub use iter::LoadStats;
+pub use pool::{OwnedScope, Pool, SharedScope};
+#[cfg(feature = "queue")]
+pub use queue::Queue;
+#[cfg(feature = "wait_stats")]
+pub use scope::WaitStats;
+pub use scope::{
+    BroadcastGuard, BroadcastStats, BroadcastTimeout, PartialBroadcast, Scope, WorkerIndex,
+};
+#[cfg(feature = "std")]
+pub use scope::{scope, scope_with};
+pub use task::TaskHandle;
 
-#[derive(Clone, Copy, Default)]
-#[repr(align(128))]
-pub struct Aligned<T>(pub T);
+macro_rules! aligned {
+    ($name:ident, $align:literal) => {
+        #[derive(Clone, Copy, Default)]
+        #[repr(align($align))]
+        pub struct $name<T>(pub T);
 
-impl<T> Deref for Aligned<T> {
-    type Target = T;
+        impl<T> Deref for $name<T> {
+            type Target = T;
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
 
-impl<T> DerefMut for Aligned<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
+        impl<T> DerefMut for $name<T> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+    };
 }
 
+aligned!(Aligned, 128);
+
+aligned!(Aligned64, 64);
+
+pub type Aligned128<T> = Aligned<T>;
+
+/// Shares a pointer into a buffer with the threads of a broadcast.
+///
+/// Only raw pointers to `Send` values can be wrapped. Dereferencing these remains unsafe and sound
+/// only if each element is accessed by at most one thread at a time during the broadcast,
+/// e.g. because the threads are handed disjoint ranges of indices.
 #[derive(Clone, Copy)]
-struct Synced<T>(T);
+struct Synced<P>(P);
+
+unsafe impl<T> Send for Synced<*mut T> where T: Send {}
 
-unsafe impl<T> Send for Synced<T> {}
+unsafe impl<T> Sync for Synced<*mut T> where T: Send {}
 
-unsafe impl<T> Sync for Synced<T> {}
+unsafe impl<T> Send for Synced<*const T> where T: Send {}
+
+unsafe impl<T> Sync for Synced<*const T> where T: Send {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::mem::{align_of, size_of};
+
+    #[test]
+    fn aligned_respects_alignment() {
+        assert_eq!(align_of::<Aligned<u8>>(), 128);
+        assert_eq!(size_of::<Aligned<u8>>(), 128);
+
+        assert_eq!(align_of::<Aligned64<u8>>(), 64);
+        assert_eq!(size_of::<Aligned64<u8>>(), 64);
+
+        assert_eq!(align_of::<Aligned128<u8>>(), 128);
+    }
+}
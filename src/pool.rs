@@ -0,0 +1,351 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::JoinHandle;
+
+use crate::{
+    config::Config,
+    scope::{Scope, State},
+};
+
+/// Keeps its worker threads alive across calls to [`Pool::scope`] until it is dropped.
+pub struct Pool {
+    state: Arc<State>,
+    handles: Vec<JoinHandle<()>>,
+    _marker: PhantomData<Cell<()>>,
+}
+
+impl Pool {
+    #[cfg(feature = "std")]
+    pub fn new(mut config: Config) -> Self {
+        let state = Arc::new(State::new(&mut config));
+
+        let handles = (1..=state.workers)
+            .map(|thread| {
+                let state = state.clone();
+
+                config
+                    .thread_builder(thread)
+                    .spawn(move || state.start(thread))
+                    .unwrap()
+            })
+            .collect();
+
+        state.wait_for_pinning();
+
+        Self {
+            state,
+            handles,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Runs the workers using `spawn` instead of spawning threads itself.
+    ///
+    /// `spawn` is called once per worker with its thread index and must eventually run the given task
+    /// on a separate thread of execution, e.g. a green thread of a custom executor.
+    /// Dropping the pool blocks until all these tasks have returned.
+    pub fn with_spawner<S>(mut config: Config, mut spawn: S) -> Self
+    where
+        S: FnMut(usize, Box<dyn FnOnce() + Send>),
+    {
+        let state = Arc::new(State::new(&mut config));
+
+        for thread in 1..=state.workers {
+            let state = state.clone();
+
+            spawn(thread, Box::new(move || state.start(thread)));
+        }
+
+        state.wait_for_pinning();
+
+        Self {
+            state,
+            handles: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn scope<F, R>(&self, f: F) -> R
+    where
+        F: for<'scope> FnOnce(Scope<'scope>) -> R,
+    {
+        self.state.cancelled.store(false, Ordering::Relaxed);
+
+        struct DrainGuard<'scope>(&'scope State);
+
+        impl Drop for DrainGuard<'_> {
+            fn drop(&mut self) {
+                self.0.drain_tasks();
+            }
+        }
+
+        let _guard = DrainGuard(&self.state);
+
+        f(Scope::new(&self.state))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Pool {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
+}
+
+impl Drop for Pool {
+    fn drop(&mut self) {
+        self.state.stop();
+
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+
+        self.state.wait_for_exit();
+    }
+}
+
+/// A single scope backed by its own [`Pool`] which can be held in a local variable.
+///
+/// This complements [`scope`](crate::scope) for driving a scope step by step, e.g. in tests.
+/// The scopes returned by [`OwnedScope::scope`] borrow it, so closures passed to them can
+/// borrow anything which outlives the respective call as usual, whereas task handles and barriers
+/// cannot outlive the `OwnedScope`. Dropping it drops any pending task and joins the workers.
+pub struct OwnedScope {
+    pool: Pool,
+}
+
+impl OwnedScope {
+    #[cfg(feature = "std")]
+    pub fn new(config: Config) -> Self {
+        Self {
+            pool: Pool::new(config),
+        }
+    }
+
+    /// Like [`Pool::scope`], each call resets the [`CancelToken`](crate::CancelToken).
+    pub fn scope(&self) -> Scope<'_> {
+        self.pool.state.cancelled.store(false, Ordering::Relaxed);
+
+        Scope::new(&self.pool.state)
+    }
+}
+
+impl Drop for OwnedScope {
+    fn drop(&mut self) {
+        self.pool.state.drain_tasks();
+    }
+}
+
+/// A cloneable handle to an [`OwnedScope`] which can be stored in long-lived structs.
+///
+/// The workers live as long as any clone of the handle. As the pool cannot be shared between threads,
+/// this uses `Rc` instead of `Arc`. The scopes returned by [`SharedScope::scope`] still borrow
+/// the handle, so task handles and barriers cannot outlive the clone they were obtained from.
+#[derive(Clone)]
+pub struct SharedScope(Rc<OwnedScope>);
+
+impl SharedScope {
+    #[cfg(feature = "std")]
+    pub fn new(config: Config) -> Self {
+        Self(Rc::new(OwnedScope::new(config)))
+    }
+
+    pub fn scope(&self) -> Scope<'_> {
+        self.0.scope()
+    }
+}
+
+impl From<OwnedScope> for SharedScope {
+    fn from(owned: OwnedScope) -> Self {
+        Self(Rc::new(owned))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::num::NonZeroUsize;
+    use std::thread;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn pool_reuses_threads() {
+        let pool = Pool::new(Config {
+            parallelism: NonZeroUsize::new(4),
+            ..Default::default()
+        });
+
+        let threads = pool.scope(|scope| scope.broadcast_with(|_thread| thread::current().id()));
+
+        for _ in 0..100 {
+            let mut counts = vec![0; 1_000];
+
+            pool.scope(|scope| {
+                assert_eq!(
+                    scope.broadcast_with(|_thread| thread::current().id()),
+                    threads
+                );
+
+                scope.for_each_dynamic(&mut counts, |count| *count += 1);
+            });
+
+            assert!(counts.into_iter().all(|count| count == 1));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn pool_resets_cancel_token() {
+        let pool = Pool::default();
+
+        pool.scope(|scope| scope.cancel_token().cancel());
+
+        pool.scope(|scope| assert!(!scope.cancel_token().is_cancelled()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn owned_and_shared_scope_reset_cancel_token() {
+        let owned = OwnedScope::new(Config::default());
+
+        owned.scope().cancel_token().cancel();
+
+        assert!(!owned.scope().cancel_token().is_cancelled());
+
+        let shared = SharedScope::from(owned);
+
+        shared.scope().cancel_token().cancel();
+
+        assert!(!shared.clone().scope().cancel_token().is_cancelled());
+    }
+
+    #[test]
+    fn pool_with_spawner_works() {
+        let handles = std::sync::Mutex::new(Vec::new());
+
+        let pool = Pool::with_spawner(
+            Config {
+                parallelism: NonZeroUsize::new(4),
+                ..Default::default()
+            },
+            |thread, task| {
+                let handle = thread::Builder::new()
+                    .name(format!("custom-worker-{thread}"))
+                    .spawn(task)
+                    .unwrap();
+
+                handles.lock().unwrap().push(handle);
+            },
+        );
+
+        let names = pool.scope(|scope| {
+            scope.broadcast_with(|_thread| thread::current().name().map(ToOwned::to_owned))
+        });
+
+        assert_eq!(names[1].as_deref(), Some("custom-worker-1"));
+        assert_eq!(names[3].as_deref(), Some("custom-worker-3"));
+
+        drop(pool);
+
+        for handle in handles.into_inner().unwrap() {
+            assert!(handle.is_finished());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn owned_scope_can_be_driven_step_by_step() {
+        let owned = OwnedScope::new(Config {
+            parallelism: NonZeroUsize::new(3),
+            ..Default::default()
+        });
+
+        let mut nums = (0..1_000).collect::<Vec<usize>>();
+
+        owned.scope().for_each_dynamic(&mut nums, |num| *num *= 2);
+
+        let scope = owned.scope();
+
+        assert_eq!(scope.parallelism(), 3);
+        assert_eq!(scope.count(&nums, |num| num % 4 == 0), 500);
+
+        let task = scope.spawn(|| 42);
+
+        assert_eq!(task.join(), 42);
+
+        drop(owned);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn shared_scope_can_be_stored_and_cloned() {
+        struct Context {
+            scope: SharedScope,
+        }
+
+        let context = Context {
+            scope: SharedScope::new(Config {
+                parallelism: NonZeroUsize::new(3),
+                ..Default::default()
+            }),
+        };
+
+        let clone = context.scope.clone();
+        drop(context);
+
+        let mut sums = Vec::new();
+
+        clone
+            .scope()
+            .fold_static(&[1, 2, 3, 4, 5], &mut sums, |sum: &mut i32, nums| {
+                *sum += nums.iter().sum::<i32>();
+            });
+
+        assert_eq!(sums.into_iter().map(|sum| sum.0).sum::<i32>(), 15);
+    }
+
+    #[test]
+    fn pool_with_spawner_runs_iterators_and_tasks() {
+        let pool = Pool::with_spawner(
+            Config {
+                parallelism: NonZeroUsize::new(3),
+                ..Default::default()
+            },
+            |_thread, task| {
+                thread::spawn(task);
+            },
+        );
+
+        let mut nums = (0..1_000).collect::<Vec<usize>>();
+
+        pool.scope(|scope| {
+            scope.for_each_dynamic(&mut nums, |num| *num *= 2);
+
+            assert_eq!(scope.count(&nums, |num| num % 4 == 0), 500);
+            assert_eq!(scope.run_on(2, || 42), 42);
+        });
+    }
+
+    #[test]
+    fn pool_is_send_but_not_sync() {
+        fn is_send<T: Send>() {}
+
+        is_send::<Pool>();
+
+        trait Ambiguous<A> {
+            fn ambiguous() {}
+        }
+
+        impl<T> Ambiguous<()> for T {}
+
+        struct IsSync;
+
+        impl<T> Ambiguous<IsSync> for T where T: Sync {}
+
+        let _ = <Pool as Ambiguous<_>>::ambiguous;
+    }
+}
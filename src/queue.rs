@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::scope::Scope;
+
+/// A concurrent source of work items like `crossbeam_queue::SegQueue`.
+pub trait Queue<T>: Sync {
+    fn pop(&self) -> Option<T>;
+}
+
+impl<T> Queue<T> for Mutex<VecDeque<T>>
+where
+    T: Send,
+{
+    fn pop(&self) -> Option<T> {
+        self.lock().unwrap().pop_front()
+    }
+}
+
+impl Scope<'_> {
+    /// Lets all threads pop items from `queue` and pass them to `f` until they observe it empty.
+    ///
+    /// Each thread stops as soon as `pop` returns `None` once, so items pushed by other threads
+    /// after that might not be processed. But items pushed by `f` itself are always processed
+    /// before this returns, as the pushing thread pops again after `f` returns.
+    pub fn drain_queue<T, Q, F>(&self, queue: &Q, f: F)
+    where
+        T: Send,
+        Q: Queue<T> + ?Sized,
+        F: Fn(usize, T) + Sync,
+    {
+        self.broadcast(|thread| {
+            while let Some(item) = queue.pop() {
+                f(thread, item);
+            }
+        });
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::scope::scope;
+
+    #[test]
+    fn drain_queue_processes_pushed_items() {
+        for parallelism in [1, 4] {
+            let queue = Mutex::new((1..=100).collect::<VecDeque<usize>>());
+            let sum = AtomicUsize::new(0);
+
+            scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.drain_queue(&queue, |_thread, item| {
+                    sum.fetch_add(item, Ordering::Relaxed);
+
+                    // Items which split further enqueue their halves.
+                    if item > 1 && item.is_multiple_of(2) {
+                        let mut queue = queue.lock().unwrap();
+
+                        queue.push_back(item / 2);
+                        queue.push_back(item / 2);
+                    }
+                });
+            });
+
+            let expected = (1..=100).map(expand).sum::<usize>();
+
+            assert!(queue.lock().unwrap().is_empty());
+            assert_eq!(sum.into_inner(), expected);
+        }
+
+        fn expand(item: usize) -> usize {
+            if item > 1 && item.is_multiple_of(2) {
+                item + 2 * expand(item / 2)
+            } else {
+                item
+            }
+        }
+    }
+}
@@ -0,0 +1,518 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Aligned, Synced, scope::Scope};
+
+const ORDERED_BLOCK_LEN: usize = 1024;
+
+/// The number of independent accumulators used by [`Scope::sum_f64`].
+const SUM_LANES: usize = 8;
+
+impl Scope<'_> {
+    pub fn reduce_static<T, A, I, F, C>(&self, work: &[T], identity: I, f: F, combine: C) -> A
+    where
+        T: Send,
+        A: Send,
+        I: Fn() -> A + Sync,
+        F: Fn(&mut A, &[T]) + Sync,
+        C: Fn(A, A) -> A,
+    {
+        let mut accum = Vec::new();
+
+        self.fold_static(work, &mut accum, |accum: &mut Option<A>, work| {
+            f(accum.get_or_insert_with(&identity), work);
+        });
+
+        accum
+            .into_iter()
+            .filter_map(|accum| accum.0)
+            .reduce(combine)
+            .unwrap_or_else(identity)
+    }
+
+    pub fn reduce_dynamic<T, A, I, F, C>(&self, work: &[T], identity: I, f: F, combine: C) -> A
+    where
+        T: Send,
+        A: Send,
+        I: Fn() -> A + Sync,
+        F: Fn(&mut A, &T) + Sync,
+        C: Fn(A, A) -> A,
+    {
+        let mut accum = Vec::new();
+
+        self.fold_dynamic(work, &mut accum, |accum: &mut Option<A>, work| {
+            f(accum.get_or_insert_with(&identity), work);
+        });
+
+        accum
+            .into_iter()
+            .filter_map(|accum| accum.0)
+            .reduce(combine)
+            .unwrap_or_else(identity)
+    }
+
+    /// Maps and combines each thread's chunk in order before combining the chunks in order,
+    /// so `combine` needs to be associative but not commutative.
+    pub fn map_reduce<T, A, M, C>(&self, work: &[T], map: M, combine: C) -> Option<A>
+    where
+        T: Sync,
+        A: Send,
+        M: Fn(&T) -> A + Sync,
+        C: Fn(A, A) -> A + Sync,
+    {
+        let mut accums = (0..=self.workers)
+            .map(|_| Aligned(None))
+            .collect::<Vec<_>>();
+
+        let accums_ptr = Synced(accums.as_mut_ptr());
+
+        self.iter_static(0..work.len(), |thread, range| {
+            let accums_ptr = accums_ptr;
+
+            let accum = unsafe { &mut *accums_ptr.0.add(thread) };
+
+            **accum = work[range].iter().map(&map).reduce(&combine);
+        });
+
+        accums
+            .into_iter()
+            .filter_map(|accum| accum.0)
+            .reduce(combine)
+    }
+
+    pub fn count<T, P>(&self, work: &[T], pred: P) -> usize
+    where
+        T: Sync,
+        P: Fn(&T) -> bool + Sync,
+    {
+        let mut counts = (0..=self.workers).map(|_| Aligned(0)).collect::<Vec<_>>();
+
+        let counts_ptr = Synced(counts.as_mut_ptr());
+
+        self.iter_static(0..work.len(), |thread, range| {
+            let counts_ptr = counts_ptr;
+
+            let count = unsafe { &mut *counts_ptr.0.add(thread) };
+
+            **count = work[range].iter().filter(|item| pred(item)).count();
+        });
+
+        // The sum cannot overflow as it is bounded by `work.len()`.
+        counts.into_iter().map(|count| count.0).sum()
+    }
+
+    /// Each thread sums its chunk using several independent accumulators to enable auto-vectorization.
+    ///
+    /// This reassociates the additions, so the result can differ from `data.iter().sum()` in the last bits
+    /// and also depends on the number of threads, c.f. [`Scope::reduce_ordered`] for reproducible results.
+    pub fn sum_f64(&self, data: &[f64]) -> f64 {
+        let mut sums = (0..=self.workers).map(|_| Aligned(0.0)).collect::<Vec<_>>();
+
+        let sums_ptr = Synced(sums.as_mut_ptr());
+
+        self.iter_static(0..data.len(), |thread, range| {
+            let sums_ptr = sums_ptr;
+
+            let sum = unsafe { &mut *sums_ptr.0.add(thread) };
+
+            **sum = sum_lanes(&data[range]);
+        });
+
+        sums.into_iter().map(|sum| sum.0).sum()
+    }
+
+    /// Each thread counts into its own bins which are summed element-wise afterwards.
+    ///
+    /// Panics if `bin` returns an index not smaller than `num_bins`.
+    pub fn histogram<T, F>(&self, work: &[T], num_bins: usize, bin: F) -> Vec<u64>
+    where
+        T: Sync,
+        F: Fn(&T) -> usize + Sync,
+    {
+        let mut bins = (0..=self.workers)
+            .map(|_| Aligned(Vec::new()))
+            .collect::<Vec<_>>();
+
+        let bins_ptr = Synced(bins.as_mut_ptr());
+
+        self.iter_static(0..work.len(), |thread, range| {
+            let bins_ptr = bins_ptr;
+
+            let bins = unsafe { &mut *bins_ptr.0.add(thread) };
+
+            bins.resize(num_bins, 0);
+
+            for item in &work[range] {
+                let bin = bin(item);
+
+                assert!(
+                    bin < num_bins,
+                    "bin index {bin} is out of range for {num_bins} bins"
+                );
+
+                bins[bin] += 1;
+            }
+        });
+
+        let mut histogram = vec![0; num_bins];
+
+        for bins in bins {
+            for (count, bin) in histogram.iter_mut().zip(bins.0) {
+                *count += bin;
+            }
+        }
+
+        histogram
+    }
+
+    /// Counts how often each key returned by `key` occurs in `work`.
+    ///
+    /// Each thread counts the keys of its chunk into its own map which are then merged
+    /// into the largest one by summing the counts on the calling thread.
+    pub fn group_count<T, K, F>(&self, work: &[T], key: F) -> HashMap<K, u64>
+    where
+        T: Sync,
+        K: Hash + Eq + Send,
+        F: Fn(&T) -> K + Sync,
+    {
+        let mut maps = self.map_chunks_static(work, |_thread, work| {
+            let mut map = HashMap::new();
+
+            for item in work {
+                *map.entry(key(item)).or_insert(0) += 1;
+            }
+
+            map
+        });
+
+        let largest = (0..maps.len())
+            .max_by_key(|&index| maps[index].len())
+            .unwrap();
+        let mut merged = maps.swap_remove(largest);
+
+        for map in maps {
+            for (key, count) in map {
+                *merged.entry(key).or_insert(0) += count;
+            }
+        }
+
+        merged
+    }
+
+    /// Returns the index of the first minimum like [`Iterator::min_by_key`].
+    pub fn min_by_key<T, K, F>(&self, work: &[T], f: F) -> Option<usize>
+    where
+        T: Sync,
+        K: Ord + Send,
+        F: Fn(&T) -> K + Sync,
+    {
+        self.select_by_key(work, f, |key, best_key| key < best_key)
+    }
+
+    /// Returns the index of the last maximum like [`Iterator::max_by_key`].
+    pub fn max_by_key<T, K, F>(&self, work: &[T], f: F) -> Option<usize>
+    where
+        T: Sync,
+        K: Ord + Send,
+        F: Fn(&T) -> K + Sync,
+    {
+        self.select_by_key(work, f, |key, best_key| key >= best_key)
+    }
+
+    fn select_by_key<T, K, F, S>(&self, work: &[T], f: F, select: S) -> Option<usize>
+    where
+        T: Sync,
+        K: Send,
+        F: Fn(&T) -> K + Sync,
+        S: Fn(&K, &K) -> bool + Sync,
+    {
+        let select = |best: Option<(usize, K)>, (index, key)| match best {
+            Some((_, ref best_key)) if !select(&key, best_key) => best,
+            _ => Some((index, key)),
+        };
+
+        let mut bests = (0..=self.workers)
+            .map(|_| Aligned(None))
+            .collect::<Vec<_>>();
+
+        let bests_ptr = Synced(bests.as_mut_ptr());
+
+        self.iter_static(0..work.len(), |thread, range| {
+            let bests_ptr = bests_ptr;
+
+            let best = unsafe { &mut *bests_ptr.0.add(thread) };
+
+            **best = range
+                .map(|index| (index, f(&work[index])))
+                .fold(None, select);
+        });
+
+        bests
+            .into_iter()
+            .filter_map(|best| best.0)
+            .fold(None, select)
+            .map(|(index, _key)| index)
+    }
+
+    /// Folds fixed-size blocks of `work` and combines their results in block order.
+    ///
+    /// As the blocks do not depend on the number of threads, the result is reproducible
+    /// across different values of `parallelism` even for non-associative operations
+    /// like floating point addition. This is slower than [`Scope::reduce_static`] though.
+    pub fn reduce_ordered<T, A, I, F, C>(&self, work: &[T], identity: I, f: F, combine: C) -> A
+    where
+        T: Sync,
+        A: Send,
+        I: Fn() -> A + Sync,
+        F: Fn(&mut A, &[T]) + Sync,
+        C: Fn(A, A) -> A,
+    {
+        let blocks = work.chunks(ORDERED_BLOCK_LEN).collect::<Vec<_>>();
+        let mut partials = blocks.iter().map(|_| None).collect::<Vec<_>>();
+
+        self.map_dynamic(&blocks, &mut partials, |block| {
+            let mut accum = identity();
+            f(&mut accum, block);
+            Some(accum)
+        });
+
+        partials
+            .into_iter()
+            .flatten()
+            .reduce(combine)
+            .unwrap_or_else(identity)
+    }
+}
+
+fn sum_lanes(data: &[f64]) -> f64 {
+    let mut lanes = [0.0; SUM_LANES];
+
+    let chunks = data.chunks_exact(SUM_LANES);
+    let rest = chunks.remainder();
+
+    for chunk in chunks {
+        for (lane, value) in lanes.iter_mut().zip(chunk) {
+            *lane += value;
+        }
+    }
+
+    lanes.into_iter().sum::<f64>() + rest.iter().sum::<f64>()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::collections::HashMap;
+    use std::num::NonZeroUsize;
+
+    use crate::scope::scope;
+
+    #[test]
+    fn reduce_static_works() {
+        let length = 1_000;
+
+        let nums = (0..length).collect::<Vec<_>>();
+
+        let sum = scope(None, |scope| {
+            scope.reduce_static(
+                &nums,
+                || 0,
+                |sum, nums| {
+                    for num in nums {
+                        *sum += num;
+                    }
+                },
+                |lhs, rhs| lhs + rhs,
+            )
+        });
+
+        assert_eq!(sum, length * (length - 1) / 2);
+    }
+
+    #[test]
+    fn reduce_dynamic_matches_reduce_static() {
+        let nums = (0..1_000).collect::<Vec<u64>>();
+
+        // Elements towards the end are much more expensive than those at the start.
+        let cost = |num: u64| (0..num * num / 1_000).fold(num, |hash, step| hash ^ (step * 31));
+
+        scope(NonZeroUsize::new(4), |scope| {
+            let dynamic = scope.reduce_dynamic(
+                &nums,
+                || 0,
+                |sum, num| *sum += cost(*num),
+                |lhs, rhs| lhs + rhs,
+            );
+
+            let static_ = scope.reduce_static(
+                &nums,
+                || 0,
+                |sum, nums| *sum += nums.iter().map(|num| cost(*num)).sum::<u64>(),
+                |lhs, rhs| lhs + rhs,
+            );
+
+            assert_eq!(dynamic, static_);
+            assert_eq!(
+                scope.reduce_dynamic(&nums[..0], || 42, |_sum, _num| (), |lhs, _rhs| lhs),
+                42
+            );
+        });
+    }
+
+    #[test]
+    fn map_reduce_preserves_order() {
+        let nums = (0..1_000).collect::<Vec<usize>>();
+
+        let expected = nums.iter().map(|num| num.to_string()).collect::<String>();
+
+        for parallelism in [1, 3, 7] {
+            scope(NonZeroUsize::new(parallelism), |scope| {
+                let concat = scope.map_reduce(&nums, |num| num.to_string(), |lhs, rhs| lhs + &rhs);
+
+                assert_eq!(concat.as_deref(), Some(&*expected));
+
+                assert_eq!(
+                    scope.map_reduce(&nums[..0], |num| *num, |lhs, rhs| lhs + rhs),
+                    None
+                );
+            });
+        }
+    }
+
+    #[test]
+    fn histogram_matches_sequential() {
+        let nums = (0..10_000)
+            .map(|num| num * num % 97)
+            .collect::<Vec<usize>>();
+
+        let mut expected = vec![0; 10];
+
+        for num in &nums {
+            expected[num % 10] += 1;
+        }
+
+        for parallelism in [1, 3, 8] {
+            let histogram = scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.histogram(&nums, 10, |num| num % 10)
+            });
+
+            assert_eq!(histogram, expected);
+        }
+
+        let histogram = scope(NonZeroUsize::new(3), |scope| {
+            scope.histogram(&nums[..0], 3, |_num| unreachable!())
+        });
+
+        assert_eq!(histogram, [0; 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "bin index 10 is out of range for 10 bins")]
+    fn histogram_rejects_out_of_range_bins() {
+        scope(NonZeroUsize::new(3), |scope| {
+            scope.histogram(&[1, 10, 3], 10, |num| *num);
+        });
+    }
+
+    #[test]
+    fn sum_f64_matches_sequential_sum() {
+        for len in [0, 7, 8, 10_003] {
+            let nums = (0..len).map(|num| num as f64 * 0.5).collect::<Vec<_>>();
+
+            for parallelism in [1, 3, 8] {
+                let sum = scope(NonZeroUsize::new(parallelism), |scope| scope.sum_f64(&nums));
+
+                // All partial sums are exactly representable, so reassociation does not change the result.
+                assert_eq!(sum, nums.iter().sum::<f64>());
+            }
+        }
+    }
+
+    #[test]
+    fn group_count_merges_keys_across_threads() {
+        let words = (0..10_000).map(|num| num * 7 % 13).collect::<Vec<usize>>();
+
+        let mut expected = HashMap::new();
+
+        for word in &words {
+            *expected.entry(*word).or_insert(0) += 1;
+        }
+
+        for parallelism in [1, 3, 8] {
+            let counts = scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.group_count(&words, |word| *word)
+            });
+
+            assert_eq!(counts, expected);
+        }
+
+        let counts = scope(NonZeroUsize::new(3), |scope| {
+            scope.group_count(&words[..0], |word| *word)
+        });
+
+        assert!(counts.is_empty());
+    }
+
+    #[test]
+    fn count_works() {
+        let nums = (0..10_000).collect::<Vec<_>>();
+
+        for parallelism in [1, 3, 8] {
+            scope(NonZeroUsize::new(parallelism), |scope| {
+                for divisor in [1, 3, 7, 20_000] {
+                    assert_eq!(
+                        scope.count(&nums, |num| num % divisor == 0),
+                        nums.iter().filter(|num| *num % divisor == 0).count()
+                    );
+                }
+
+                assert_eq!(scope.count(&nums[..0], |_num| true), 0);
+            });
+        }
+    }
+
+    #[test]
+    fn min_max_by_key_work() {
+        let nums = (0..10_000).map(|num| num % 100).collect::<Vec<_>>();
+
+        for parallelism in [1, 3, 8] {
+            scope(NonZeroUsize::new(parallelism), |scope| {
+                assert_eq!(scope.min_by_key(&nums, |num| *num), Some(0));
+                assert_eq!(scope.max_by_key(&nums, |num| *num), Some(9_999));
+
+                assert_eq!(scope.min_by_key(&nums, |num| *num / 50), Some(0));
+                assert_eq!(scope.max_by_key(&nums, |num| *num / 50), Some(9_999));
+
+                assert_eq!(scope.min_by_key(&nums, |num| (*num + 1) % 100), Some(99));
+                assert_eq!(scope.max_by_key(&nums, |num| (*num + 1) % 100), Some(9_998));
+
+                assert_eq!(scope.min_by_key(&nums[..0], |num| *num), None);
+                assert_eq!(scope.max_by_key(&nums[..0], |num| *num), None);
+            });
+        }
+    }
+
+    #[test]
+    fn reduce_ordered_is_reproducible() {
+        let nums = (0..100_000)
+            .map(|num| 1.0 / (1.0 + num as f64))
+            .collect::<Vec<_>>();
+
+        let sums = [1, 3, 8].map(|parallelism| {
+            scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.reduce_ordered(
+                    &nums,
+                    || 0.0,
+                    |sum, nums| {
+                        for num in nums {
+                            *sum += num;
+                        }
+                    },
+                    |lhs, rhs| lhs + rhs,
+                )
+            })
+        });
+
+        assert_eq!(sums[0].to_bits(), sums[1].to_bits());
+        assert_eq!(sums[0].to_bits(), sums[2].to_bits());
+    }
+}
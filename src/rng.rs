@@ -0,0 +1,69 @@
+use rand_core::SeedableRng;
+
+use crate::{Aligned, scope::Scope};
+
+impl Scope<'_> {
+    /// Derives one generator per thread from `seed` using SplitMix64.
+    ///
+    /// The generators are reproducible given `seed` and the parallelism of the scope,
+    /// i.e. changing the parallelism changes which stream is assigned to which thread.
+    /// Each generator is created on the thread it is meant for.
+    pub fn per_thread_rng<R>(&self, seed: u64) -> Vec<Aligned<R>>
+    where
+        R: SeedableRng + Send,
+    {
+        self.broadcast_with(|thread| Aligned(R::seed_from_u64(split_mix64(seed, thread))))
+    }
+}
+
+fn split_mix64(seed: u64, index: usize) -> u64 {
+    let mut state = seed.wrapping_add((index as u64 + 1).wrapping_mul(0x9e3779b97f4a7c15));
+
+    state = (state ^ (state >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    state = (state ^ (state >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+    state ^ (state >> 31)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use std::num::NonZeroUsize;
+
+    use crate::scope::scope;
+
+    #[derive(Debug, PartialEq)]
+    struct Seed([u8; 8]);
+
+    impl SeedableRng for Seed {
+        type Seed = [u8; 8];
+
+        fn from_seed(seed: [u8; 8]) -> Self {
+            Self(seed)
+        }
+    }
+
+    #[test]
+    fn per_thread_rng_is_reproducible() {
+        let seeds = |seed| {
+            scope(NonZeroUsize::new(4), |scope| {
+                scope
+                    .per_thread_rng::<Seed>(seed)
+                    .into_iter()
+                    .map(|rng| rng.0)
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        let first = seeds(42);
+
+        assert_eq!(first.len(), 4);
+        assert_eq!(first, seeds(42));
+        assert_ne!(first, seeds(43));
+
+        for (index, seed) in first.iter().enumerate() {
+            assert!(first[index + 1..].iter().all(|other| other != seed));
+        }
+    }
+}
@@ -1,111 +1,592 @@
+use std::any::Any;
 use std::cell::Cell;
-use std::hint::spin_loop;
 use std::marker::PhantomData;
 use std::mem::transmute;
 use std::num::NonZeroUsize;
+use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
 use std::ptr;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::Aligned;
+#[cfg(feature = "std")]
+use crate::builder::Builder;
+use crate::sync::{
+    AtomicBool, AtomicUsize, Ordering, Thread, current_thread, fence, park_thread, spin_loop,
+    yield_now,
+};
+use crate::{Aligned, Synced, affinity::pin_current_thread, config::Config, task::TaskSlot};
 
 pub struct Scope<'scope> {
     pub(crate) state: &'scope State,
+    pub(crate) workers: usize,
+    /// Whether the dynamic iterators stop once the [`CancelToken`] is tripped, c.f. [`Scope::uncancellable`].
+    ///
+    /// [`CancelToken`]: crate::CancelToken
+    pub(crate) cancellable: bool,
     _marker: PhantomData<*mut ()>,
 }
 
+impl<'scope> Scope<'scope> {
+    pub(crate) fn new(state: &'scope State) -> Self {
+        Self {
+            state,
+            workers: state.workers,
+            cancellable: true,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a view of this scope which dispatches work only to the first `workers` workers.
+    ///
+    /// Views can only restrict the active workers further, never extend them again.
+    ///
+    /// Work is split as if the scope had only `workers` workers, so accumulators passed to e.g.
+    /// [`Scope::fold_static_into`] must be sized for the active workers of the returned view.
+    /// The remaining workers are neither woken nor waited for by broadcasts of the returned view,
+    /// so parked workers stay asleep until a broadcast dispatches to them again.
+    /// When spinning instead of parking, they still observe each broadcast but skip it without touching the work.
+    pub fn with_active_workers(&self, workers: usize) -> Self {
+        Self {
+            state: self.state,
+            workers: workers.min(self.workers),
+            cancellable: self.cancellable,
+            _marker: PhantomData,
+        }
+    }
+}
+
 impl Scope<'_> {
+    pub fn parallelism(&self) -> usize {
+        self.workers + 1
+    }
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Returns how often all threads of this scope have waited so far.
+    ///
+    /// Many yields or parks compared to spins indicate that the workers are oversubscribed or starved.
+    #[cfg(feature = "wait_stats")]
+    pub fn wait_stats(&self) -> WaitStats {
+        let state = self.state;
+
+        WaitStats {
+            spins: state.spins.load(Ordering::Relaxed),
+            yields: state.yields.load(Ordering::Relaxed),
+            parks: state.parks.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the index of the calling thread if it is currently running a broadcast of this scope.
+    ///
+    /// This returns `None` on threads which are not taking part in this scope, including
+    /// threads running broadcasts of unrelated or nested scopes. Nested scopes overwrite
+    /// the thread-local backing this for the duration of their broadcasts and restore it afterwards.
+    ///
+    /// As the scope cannot be shared with the closures passed to its broadcasts,
+    /// these should use the handle returned by [`Scope::worker_index`] instead.
+    pub fn in_worker(&self) -> Option<usize> {
+        self.worker_index().get()
+    }
+
+    /// Returns a handle which can be passed into the closures of broadcasts of this scope
+    /// to query the index of the thread running them, c.f. [`Scope::in_worker`].
+    pub fn worker_index(&self) -> WorkerIndex<'_> {
+        WorkerIndex { state: self.state }
+    }
+
+    /// Returns the CPU to which worker `thread` was pinned, if any.
+    pub fn pinned_cpu(&self, thread: usize) -> Option<usize> {
+        thread
+            .checked_sub(1)
+            .and_then(|worker| self.state.pinned_cpus.get(worker))
+            .and_then(|cpu| cpu.get().copied())
+    }
+
     pub fn broadcast<F>(&self, f: F)
     where
         F: Fn(usize) + Sync,
     {
-        self.broadcast_impl(&f);
+        self.broadcast_dyn(&f);
+    }
+
+    /// Like [`Scope::broadcast`], but takes an already type-erased closure.
+    ///
+    /// `broadcast` itself only erases the type of its closure before delegating here,
+    /// so this mainly helps callers which already hold a `&dyn Fn`. It does not reduce binary size:
+    /// `benches/dyn_size.rs` measures 64 distinct closures to take about the same space either way.
+    pub fn broadcast_dyn(&self, f: &(dyn Fn(usize) + Sync)) {
+        self.broadcast_impl(f);
+    }
+
+    pub fn broadcast_with<T, F>(&self, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(usize) -> T + Sync,
+    {
+        let len = self.workers + 1;
+
+        let mut results = Vec::<T>::with_capacity(len);
+        let results_ptr = Synced(results.as_mut_ptr());
+
+        self.broadcast(|thread| {
+            let results_ptr = results_ptr;
+
+            unsafe {
+                results_ptr.0.add(thread).write(f(thread));
+            }
+        });
+
+        // SAFETY: Each thread has initialized its own slot when `broadcast` returns.
+        unsafe {
+            results.set_len(len);
+        }
+
+        results
+    }
+
+    /// Like [`Scope::broadcast_with`], but fails if `f` fails on any thread.
+    ///
+    /// If several threads fail, the error of the lowest-numbered one is returned.
+    /// All other results, successful or not, are discarded.
+    pub fn try_broadcast_with<T, E, F>(&self, f: F) -> Result<Vec<T>, E>
+    where
+        T: Send,
+        E: Send,
+        F: Fn(usize) -> Result<T, E> + Sync,
+    {
+        self.broadcast_with(f).into_iter().collect()
+    }
+
+    /// Like [`Scope::broadcast_with`], but catches panics to retain the results of the other threads.
+    ///
+    /// The slots are initialized to `None` before the broadcast, so after a panic, each slot either
+    /// holds the result of a thread which completed successfully or is still `None`.
+    pub fn catch_broadcast_with<T, F>(&self, f: F) -> Result<Vec<T>, PartialBroadcast<T>>
+    where
+        T: Send,
+        F: Fn(usize) -> T + Sync,
+    {
+        let panic = Mutex::new(None);
+
+        let mut results = (0..=self.workers).map(|_| None).collect::<Vec<_>>();
+        let results_ptr = Synced(results.as_mut_ptr());
+
+        self.broadcast(|thread| {
+            let results_ptr = results_ptr;
+
+            match catch_unwind(AssertUnwindSafe(|| f(thread))) {
+                Ok(result) => unsafe {
+                    *results_ptr.0.add(thread) = Some(result);
+                },
+                Err(payload) => {
+                    panic.lock().unwrap().get_or_insert(payload);
+                }
+            }
+        });
+
+        match panic.into_inner().unwrap() {
+            None => Ok(results.into_iter().flatten().collect()),
+            Some(panic) => Err(PartialBroadcast { results, panic }),
+        }
+    }
+
+    /// Dispatches `f` to the workers without running it on the calling thread.
+    ///
+    /// The calling thread is free to do independent work until [`BroadcastGuard::wait`] is called,
+    /// which then runs `f(0)` itself and blocks until the workers have finished.
+    /// Dropping the guard without waiting behaves the same, except that `f(0)` is skipped when unwinding.
+    ///
+    /// `f` must be `'static` as it is owned by the guard and the guard could be leaked
+    /// while the workers are still running it. Leaking the guard is memory-safe as `f` is leaked as well,
+    /// but any further broadcast of this scope will panic as the deferred one never finishes.
+    pub fn broadcast_deferred<F>(&self, f: F) -> BroadcastGuard<'_, F>
+    where
+        F: Fn(usize) + Sync + 'static,
+    {
+        let f = Box::new(f);
+
+        let dispatched = (self.workers != 0).then(|| {
+            // SAFETY: `f` is heap-allocated and only dropped by the guard after waiting for the workers.
+            // If the guard is leaked instead, so is `f`.
+            unsafe { self.dispatch(transmute::<&Work, &'static Work>(&*f)) }
+        });
+
+        BroadcastGuard {
+            scope: self,
+            f: Some(f),
+            dispatched,
+            start: self.state.on_broadcast.is_some().then(Instant::now),
+        }
     }
 
     fn broadcast_impl(&self, f: &Work) {
-        let state = self.state;
+        let start = self.state.on_broadcast.is_some().then(Instant::now);
+
+        // Without any active workers, there is nothing to dispatch and nothing to wait for.
+        if self.workers == 0 {
+            {
+                let _current = CurrentThread::enter(self.state, 0);
+
+                f(0);
+            }
+
+            self.finish(None, start);
+
+            return;
+        }
 
-        // SAFETY: `_guard` will reset `state.work` before this function returns,
+        // SAFETY: `join_dispatched` will reset `state.work` before this function returns,
         // but only after all pending workers are finished.
-        unsafe {
-            state.work.set(transmute::<&Work, &'static Work>(&f));
+        let dispatched = unsafe { self.dispatch(transmute::<&Work, &'static Work>(f)) };
+        let generation = dispatched.generation;
+
+        self.join_dispatched(dispatched, Some(f));
+
+        self.finish(Some(generation), start);
+    }
+
+    /// Hands `f` to the workers without running it on the calling thread.
+    ///
+    /// # Safety
+    ///
+    /// `f` must stay valid until `Scope::join_dispatched` is called with the returned value.
+    unsafe fn dispatch(&self, f: &'static Work) -> Dispatched {
+        let state = self.state;
+
+        assert!(
+            !state.broadcasting.replace(true),
+            "broadcast must not be called from within a broadcast of the same scope"
+        );
+
+        state.work.set(f);
+
+        #[cfg(feature = "tracing")]
+        let span = {
+            let span = tracing::debug_span!(
+                "broadcast",
+                generation = state.generation.load(Ordering::Relaxed).wrapping_add(1)
+            );
+
+            *state.span.lock().unwrap() = span.clone();
+
+            span
+        };
+
+        let generation = state.generation.load(Ordering::Relaxed).wrapping_add(1);
+        for dispatched in &state.dispatched[..self.workers] {
+            dispatched.store(generation, Ordering::Relaxed);
         }
+        state.generation.store(generation, Ordering::Release);
+        state.unpark_active(self.workers);
 
-        state.pending.store(self.state.workers, Ordering::Relaxed);
-        state.generation.fetch_add(1, Ordering::Release);
+        Dispatched {
+            generation,
+            #[cfg(feature = "tracing")]
+            span,
+        }
+    }
+
+    /// Runs `f(0)` on the calling thread, if given, and waits for the workers to finish a dispatched broadcast.
+    fn join_dispatched(&self, dispatched: Dispatched, f: Option<&Work>) {
+        let state = self.state;
 
-        struct ResetGuard<'scope>(&'scope State);
+        struct ResetGuard<'scope>(&'scope State, usize, usize);
 
         impl Drop for ResetGuard<'_> {
             fn drop(&mut self) {
-                let state = self.0;
+                let (state, generation, active) = (self.0, self.1, self.2);
 
                 let mut wait_count = 0;
 
-                while state.pending.load(Ordering::Acquire) != 0 {
-                    wait(&mut wait_count);
+                let start = state.broadcast_timeout.map(|_| Instant::now());
+                let mut reported = 0;
+
+                // Each worker only writes its own slot instead of all of them contending on a shared counter.
+                // Inactive workers skip the broadcast without writing theirs, so only the active ones are awaited.
+                for done in &state.done[..active] {
+                    while done.load(Ordering::Acquire) != generation {
+                        state.wait(&mut wait_count);
+
+                        if let (Some(timeout), Some(start)) = (state.broadcast_timeout, start) {
+                            state.report_timeout(generation, active, timeout, start, &mut reported);
+                        }
+                    }
                 }
 
                 state.work.set(STOP);
+                state.broadcasting.set(false);
+
+                if thread::panicking() {
+                    // The panic raised by thread 0 takes precedence,
+                    // so any panic raised by the workers is discarded.
+                    drop(state.panic.lock().unwrap().take());
+                }
             }
         }
 
-        let _guard = ResetGuard(state);
+        let _guard = ResetGuard(state, dispatched.generation, self.workers);
+
+        if let Some(f) = f {
+            let _current = CurrentThread::enter(state, 0);
+
+            #[cfg(feature = "tracing")]
+            let _span = work_span(&dispatched.span, 0);
+
+            f(0);
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        let _ = dispatched;
+    }
+
+    /// Reports the broadcast to [`Config::on_broadcast`] and resumes any panic raised by the workers.
+    fn finish(&self, generation: Option<usize>, start: Option<Instant>) {
+        let state = self.state;
+
+        if let (Some(on_broadcast), Some(start)) = (&state.on_broadcast, start) {
+            on_broadcast(BroadcastStats {
+                generation: generation.unwrap_or_else(|| state.generation.load(Ordering::Relaxed)),
+                workers: self.workers,
+                wall_time: start.elapsed(),
+            });
+        }
 
-        f(0);
+        if let Some(panic) = state.panic.lock().unwrap().take() {
+            resume_unwind(panic);
+        }
     }
 }
 
-pub fn scope<F, R>(parallelism: Option<NonZeroUsize>, f: F) -> R
+struct Dispatched {
+    generation: usize,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+/// Returned by [`Scope::broadcast_deferred`] to wait for the deferred broadcast.
+#[must_use = "dropping the guard immediately waits for the broadcast"]
+pub struct BroadcastGuard<'a, F>
 where
-    F: for<'scope> FnOnce(Scope<'scope>) -> R,
+    F: Fn(usize) + Sync + 'static,
+{
+    scope: &'a Scope<'a>,
+    f: Option<Box<F>>,
+    dispatched: Option<Dispatched>,
+    start: Option<Instant>,
+}
+
+impl<F> BroadcastGuard<'_, F>
+where
+    F: Fn(usize) + Sync + 'static,
 {
-    let parallelism = parallelism
-        .or_else(|| thread::available_parallelism().ok())
-        .map_or(1, NonZeroUsize::get);
+    /// Runs `f(0)` on the calling thread and blocks until the workers have finished.
+    pub fn wait(mut self) {
+        self.complete(true);
+    }
 
-    let state = &State {
-        workers: parallelism - 1,
-        work: Cell::new(STOP),
-        pending: Aligned(AtomicUsize::new(0)),
-        generation: Aligned(AtomicUsize::new(0)),
-    };
+    fn complete(&mut self, run: bool) {
+        let Some(f) = self.f.take() else {
+            return;
+        };
 
-    thread::scope(|scope| {
-        for thread in 1..parallelism {
-            thread::Builder::new()
-                .name(format!("fork-join-scope-worker-{thread}"))
-                .spawn_scoped(scope, move || state.worker(thread))
-                .unwrap();
-        }
+        let generation = match self.dispatched.take() {
+            Some(dispatched) => {
+                let generation = dispatched.generation;
 
-        struct StopGuard<'scope>(&'scope State);
+                self.scope
+                    .join_dispatched(dispatched, run.then_some(&*f as &Work));
 
-        impl Drop for StopGuard<'_> {
-            fn drop(&mut self) {
-                let state = self.0;
+                Some(generation)
+            }
+            None => {
+                if run {
+                    let _current = CurrentThread::enter(self.scope.state, 0);
 
-                state.work.set(STOP);
+                    f(0);
+                }
 
-                state.generation.fetch_add(1, Ordering::Release);
+                None
             }
+        };
+
+        drop(f);
+
+        if run {
+            self.scope.finish(generation, self.start);
+        }
+    }
+}
+
+impl<F> Drop for BroadcastGuard<'_, F>
+where
+    F: Fn(usize) + Sync + 'static,
+{
+    fn drop(&mut self) {
+        self.complete(!thread::panicking());
+    }
+}
+
+/// The results of a broadcast during which at least one thread panicked.
+pub struct PartialBroadcast<T> {
+    /// The results indexed by thread, `None` for those threads which panicked.
+    pub results: Vec<Option<T>>,
+    /// The payload of the first panic.
+    pub panic: Box<dyn Any + Send>,
+}
+
+/// Returned by [`Scope::worker_index`].
+#[derive(Clone, Copy)]
+pub struct WorkerIndex<'scope> {
+    state: &'scope State,
+}
+
+impl WorkerIndex<'_> {
+    /// Returns the index of the calling thread if it is currently running a broadcast of the scope.
+    pub fn get(&self) -> Option<usize> {
+        CURRENT_THREAD
+            .get()
+            .filter(|(state, _thread)| ptr::eq(*state, self.state))
+            .map(|(_state, thread)| thread)
+    }
+}
+
+/// Returned by [`Scope::wait_stats`].
+#[cfg(feature = "wait_stats")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitStats {
+    /// The number of waits which spun before checking again.
+    pub spins: usize,
+    /// The number of waits which yielded after exhausting the spin limit.
+    pub yields: usize,
+    /// The number of times idle workers were parked.
+    pub parks: usize,
+}
+
+/// Passed to [`Config::on_broadcast_timeout`] whenever [`Config::broadcast_timeout`] elapses.
+pub struct BroadcastTimeout {
+    /// The generation of the broadcast, c.f. [`BroadcastStats::generation`].
+    pub generation: usize,
+    /// Measured by the calling thread since it started waiting for the workers.
+    pub elapsed: Duration,
+    /// The indices of the workers which are still running the broadcast.
+    pub pending: Vec<usize>,
+}
+
+/// Passed to [`Config::on_broadcast`] after each broadcast.
+pub struct BroadcastStats {
+    /// Counts the dispatched broadcasts of a scope starting from one.
+    ///
+    /// Broadcasts without any active workers run directly on the calling thread
+    /// and report the generation of the last dispatched broadcast instead.
+    pub generation: usize,
+    pub workers: usize,
+    /// Measured by the calling thread from dispatching the work until all workers have finished.
+    pub wall_time: Duration,
+}
+
+#[cfg(feature = "std")]
+pub fn scope<F, R>(parallelism: Option<NonZeroUsize>, f: F) -> R
+where
+    F: for<'scope> FnOnce(Scope<'scope>) -> R,
+{
+    let mut builder = Builder::default();
+
+    if let Some(parallelism) = parallelism {
+        builder = builder.parallelism(parallelism);
+    }
+
+    builder.build_scope(f)
+}
+
+#[cfg(feature = "std")]
+pub fn scope_with<F, R>(mut config: Config, f: F) -> R
+where
+    F: for<'scope> FnOnce(Scope<'scope>) -> R,
+{
+    scope_with_state(&State::new(&mut config), &config, f)
+}
+
+#[cfg(feature = "std")]
+fn scope_with_state<F, R>(state: &State, config: &Config, f: F) -> R
+where
+    F: for<'scope> FnOnce(Scope<'scope>) -> R,
+{
+    thread::scope(|scope| {
+        for thread in 1..=state.workers {
+            config
+                .thread_builder(thread)
+                .spawn_scoped(scope, move || state.start(thread))
+                .unwrap();
         }
 
+        state.wait_for_pinning();
+
         let _guard = StopGuard(state);
 
-        f(Scope {
-            state,
-            _marker: PhantomData,
-        })
+        f(Scope::new(state))
     })
 }
 
+/// Drains pending tasks and stops the workers when dropped, even when unwinding.
+struct StopGuard<'scope>(&'scope State);
+
+impl Drop for StopGuard<'_> {
+    fn drop(&mut self) {
+        self.0.drain_tasks();
+        self.0.stop();
+    }
+}
+
 #[repr(C, align(128))]
 pub(crate) struct State {
     pub(crate) workers: usize,
+    pub(crate) spin_limit: u32,
     work: Cell<&'static Work<'static>>,
-    pending: Aligned<AtomicUsize>,
+    broadcasting: Cell<bool>,
+    /// The last generation dispatched to each worker, which skips all others without touching `work`.
+    dispatched: Box<[Aligned<AtomicUsize>]>,
+    /// The last generation finished by each worker.
+    done: Box<[Aligned<AtomicUsize>]>,
+    /// The number of workers which have not yet attempted to pin themselves.
+    pinning: Aligned<AtomicUsize>,
     generation: Aligned<AtomicUsize>,
+    initial_generation: usize,
+    panic: Mutex<Option<Box<dyn Any + Send>>>,
+    park: bool,
+    sleeping: Aligned<AtomicUsize>,
+    threads: Box<[OnceLock<Thread>]>,
+    pin_threads: bool,
+    pinned_cpus: Box<[OnceLock<usize>]>,
+    /// Not part of the broadcast protocol and hence never model-checked, c.f. [`Scope::cancelled`].
+    pub(crate) cancelled: std::sync::atomic::AtomicBool,
+    pub(crate) barrier_count: Aligned<AtomicUsize>,
+    pub(crate) barrier_generation: Aligned<AtomicUsize>,
+    running: AtomicUsize,
+    pub(crate) task: TaskSlot,
+    pub(crate) has_task: AtomicBool,
+    pub(crate) running_tasks: AtomicUsize,
+    /// The tasks passed to [`Scope::run_on`] for each worker.
+    pub(crate) targets: Box<[TaskSlot]>,
+    pub(crate) has_target: Box<[Aligned<AtomicBool>]>,
+    on_broadcast: Option<Box<dyn Fn(BroadcastStats) + Send + Sync>>,
+    /// Empty unless [`Config::track_load`] is set.
+    pub(crate) load: Box<[Aligned<AtomicUsize>]>,
+    pub(crate) load_threads: AtomicUsize,
+    thread_init: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    thread_deinit: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    broadcast_timeout: Option<Duration>,
+    on_broadcast_timeout: Option<Box<dyn Fn(BroadcastTimeout) + Send + Sync>>,
+    #[cfg(feature = "wait_stats")]
+    spins: AtomicUsize,
+    #[cfg(feature = "wait_stats")]
+    yields: AtomicUsize,
+    #[cfg(feature = "wait_stats")]
+    parks: AtomicUsize,
+    #[cfg(feature = "tracing")]
+    span: Mutex<tracing::Span>,
 }
 
 unsafe impl Send for State {}
@@ -114,19 +595,50 @@ unsafe impl Sync for State {}
 
 impl State {
     fn worker(&self, thread: usize) {
-        let mut last_generation = 0;
+        if self.park {
+            let _ = self.threads[thread - 1].set(current_thread());
+        }
+
+        let mut last_generation = self.initial_generation;
+
+        // Broadcasts arriving while still spinning make the next wait start with shorter spins
+        // whereas those arriving only after yielding or parking make it start closer to these.
+        let mut initial_wait_count = 0;
 
         loop {
-            let mut wait_count = 0;
+            let mut wait_count = initial_wait_count;
+            let mut slow = false;
 
             loop {
                 let curr_generation = self.generation.load(Ordering::Acquire);
 
                 if last_generation != curr_generation {
                     last_generation = curr_generation;
+
+                    // Not dispatched to this worker, c.f. `Scope::with_active_workers`.
+                    if self.dispatched[thread - 1].load(Ordering::Relaxed) != curr_generation {
+                        continue;
+                    }
+
+                    initial_wait_count = if slow {
+                        (initial_wait_count + 1).min(self.spin_limit)
+                    } else {
+                        initial_wait_count.saturating_sub(1)
+                    };
+
                     break;
+                } else if self.run_target(thread) || self.run_task() {
+                    wait_count = 0;
+                    slow = false;
                 } else {
-                    wait(&mut wait_count);
+                    // Parking does not advance `wait_count`, so this has to be recorded separately.
+                    slow |= wait_count >= self.spin_limit;
+
+                    if self.park && wait_count >= self.spin_limit {
+                        self.park(thread, last_generation);
+                    } else {
+                        self.wait(&mut wait_count);
+                    }
                 }
             }
 
@@ -136,68 +648,1236 @@ impl State {
                 return;
             }
 
-            work(thread);
+            if let Err(panic) = catch_unwind(AssertUnwindSafe(|| {
+                let _current = CurrentThread::enter(self, thread);
+
+                #[cfg(feature = "tracing")]
+                let _span = work_span(&self.span.lock().unwrap(), thread);
 
-            self.pending.fetch_sub(1, Ordering::Release);
+                work(thread);
+            })) {
+                self.panic.lock().unwrap().get_or_insert(panic);
+            }
+
+            self.done[thread - 1].store(last_generation, Ordering::Release);
         }
     }
 }
 
-type Work<'work> = dyn Fn(usize) + Sync + 'work;
+impl State {
+    pub(crate) fn new(config: &mut Config) -> Self {
+        let parallelism = config
+            .parallelism
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get);
 
-static STOP: &Work = &|_thread| ();
+        let workers = parallelism - 1;
 
-fn wait(wait_count: &mut usize) {
-    if *wait_count < 6 {
-        for _ in 0..1 << *wait_count {
-            spin_loop();
+        Self {
+            workers,
+            spin_limit: config.spin_limit.min(16),
+            park: config.park,
+            work: Cell::new(STOP),
+            broadcasting: Cell::new(false),
+            dispatched: (0..workers).map(|_| Aligned(AtomicUsize::new(0))).collect(),
+            done: (0..workers).map(|_| Aligned(AtomicUsize::new(0))).collect(),
+            pinning: Aligned(AtomicUsize::new(if config.pin_threads {
+                workers
+            } else {
+                0
+            })),
+            generation: Aligned(AtomicUsize::new(0)),
+            initial_generation: 0,
+            panic: Mutex::new(None),
+            sleeping: Aligned(AtomicUsize::new(0)),
+            threads: (0..workers).map(|_| OnceLock::new()).collect(),
+            pin_threads: config.pin_threads,
+            pinned_cpus: (0..workers).map(|_| OnceLock::new()).collect(),
+            cancelled: std::sync::atomic::AtomicBool::new(false),
+            barrier_count: Aligned(AtomicUsize::new(0)),
+            barrier_generation: Aligned(AtomicUsize::new(0)),
+            running: AtomicUsize::new(workers),
+            task: Mutex::new(None),
+            has_task: AtomicBool::new(false),
+            running_tasks: AtomicUsize::new(0),
+            targets: (0..workers).map(|_| Mutex::new(None)).collect(),
+            has_target: (0..workers)
+                .map(|_| Aligned(AtomicBool::new(false)))
+                .collect(),
+            on_broadcast: config.on_broadcast.take(),
+            load: if config.track_load {
+                (0..parallelism)
+                    .map(|_| Aligned(AtomicUsize::new(0)))
+                    .collect()
+            } else {
+                Box::default()
+            },
+            load_threads: AtomicUsize::new(0),
+            thread_init: config.thread_init.take(),
+            thread_deinit: config.thread_deinit.take(),
+            broadcast_timeout: config.broadcast_timeout,
+            on_broadcast_timeout: config.on_broadcast_timeout.take(),
+            #[cfg(feature = "wait_stats")]
+            spins: AtomicUsize::new(0),
+            #[cfg(feature = "wait_stats")]
+            yields: AtomicUsize::new(0),
+            #[cfg(feature = "wait_stats")]
+            parks: AtomicUsize::new(0),
+            #[cfg(feature = "tracing")]
+            span: Mutex::new(tracing::Span::none()),
         }
-    } else {
-        thread::yield_now();
     }
 
-    *wait_count += 1;
-}
+    #[cfg(all(test, feature = "std", not(loom)))]
+    fn with_generation(mut self, generation: usize) -> Self {
+        self.generation = Aligned(AtomicUsize::new(generation));
+        self.initial_generation = generation;
+        for done in self.done.iter_mut().chain(&mut self.dispatched) {
+            *done = Aligned(AtomicUsize::new(generation));
+        }
+        self
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub(crate) fn start(&self, thread: usize) {
+        if self.pin_threads {
+            self.pin(thread);
+        }
 
-    #[test]
-    fn broadcast_works() {
-        let parallelism = NonZeroUsize::new(10).unwrap();
+        if let Some(thread_init) = &self.thread_init
+            && let Err(panic) = catch_unwind(AssertUnwindSafe(|| thread_init(thread)))
+        {
+            // Resumed by a subsequent broadcast instead of leaving it waiting for this worker.
+            self.panic.lock().unwrap().get_or_insert(panic);
+        }
 
-        let mut counts = (0..parallelism.get())
-            .map(|_| AtomicUsize::new(0))
+        struct ExitGuard<'scope>(&'scope State);
+
+        impl Drop for ExitGuard<'_> {
+            fn drop(&mut self) {
+                self.0.running.fetch_sub(1, Ordering::Release);
+            }
+        }
+
+        let _guard = ExitGuard(self);
+
+        self.worker(thread);
+
+        if let Some(thread_deinit) = &self.thread_deinit {
+            thread_deinit(thread);
+        }
+    }
+
+    /// Creates the state for a group of `workers` threads taken from this scope, c.f. [`Scope::fork`].
+    ///
+    /// The group has its own broadcast and barrier state, so it can broadcast independently of other groups.
+    pub(crate) fn group(&self, workers: usize) -> Self {
+        Self::new(&mut Config {
+            parallelism: NonZeroUsize::new(workers + 1),
+            spin_limit: self.spin_limit,
+            park: self.park,
+            ..Default::default()
+        })
+    }
+
+    /// Runs `f` as thread 0 of this group and stops its workers afterwards.
+    pub(crate) fn lead_group<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(Scope<'_>) -> R,
+    {
+        let _guard = StopGuard(self);
+
+        f(Scope::new(self))
+    }
+
+    /// Runs worker `thread` of this group until [`State::lead_group`] returns.
+    pub(crate) fn join_group(&self, thread: usize) {
+        self.worker(thread);
+    }
+
+    pub(crate) fn wait_for_exit(&self) {
+        let mut wait_count = 0;
+
+        while self.running.load(Ordering::Acquire) != 0 {
+            self.wait(&mut wait_count);
+        }
+    }
+
+    /// Reports once per elapsed `timeout` which of the first `active` workers are still running the given broadcast.
+    ///
+    /// This cannot panic instead as the workers might still access the closure owned by the caller.
+    fn report_timeout(
+        &self,
+        generation: usize,
+        active: usize,
+        timeout: Duration,
+        start: Instant,
+        reported: &mut u32,
+    ) {
+        let elapsed = start.elapsed();
+
+        // Stops reporting once the next deadline is not representable anymore.
+        let Some(deadline) = reported
+            .checked_add(1)
+            .and_then(|reports| timeout.checked_mul(reports))
+        else {
+            return;
+        };
+
+        if elapsed < deadline {
+            return;
+        }
+
+        *reported += 1;
+
+        let pending = self.done[..active]
+            .iter()
+            .enumerate()
+            .filter(|(_worker, done)| done.load(Ordering::Relaxed) != generation)
+            .map(|(worker, _done)| worker + 1)
             .collect::<Vec<_>>();
 
-        scope(Some(parallelism), |scope| {
-            scope.broadcast(|thread| {
-                counts[thread].fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            generation,
+            ?elapsed,
+            ?pending,
+            "broadcast is still waiting for {} of {} workers",
+            pending.len(),
+            active
+        );
+
+        if let Some(on_broadcast_timeout) = &self.on_broadcast_timeout {
+            on_broadcast_timeout(BroadcastTimeout {
+                generation,
+                elapsed,
+                pending,
             });
-        });
+        }
+    }
 
-        for count in &mut counts {
-            assert_eq!(*count.get_mut(), 1);
+    pub(crate) fn wait_for_pinning(&self) {
+        if self.pin_threads {
+            let mut wait_count = 0;
+
+            while self.pinning.load(Ordering::Acquire) != 0 {
+                self.wait(&mut wait_count);
+            }
         }
     }
 
-    #[test]
-    fn scope_is_neither_send_nor_sync() {
-        trait Ambiguous<A> {
-            fn ambiguous() {}
+    /// Must only be called by the thread owning the scope after its last broadcast has returned.
+    ///
+    /// As `broadcast_impl` waits for all workers to finish their work even when unwinding,
+    /// no worker can observe `STOP` for a generation which still has work pending.
+    pub(crate) fn stop(&self) {
+        self.work.set(STOP);
+
+        let generation = self.generation.load(Ordering::Relaxed).wrapping_add(1);
+        for dispatched in &self.dispatched {
+            dispatched.store(generation, Ordering::Relaxed);
         }
+        self.generation.store(generation, Ordering::Release);
+        self.unpark();
+    }
 
-        impl<T> Ambiguous<()> for T {}
+    fn pin(&self, thread: usize) {
+        if pin_current_thread(thread) {
+            let _ = self.pinned_cpus[thread - 1].set(thread);
+        }
 
-        struct IsSend;
+        self.pinning.fetch_sub(1, Ordering::Release);
+    }
 
-        impl<T> Ambiguous<IsSend> for T where T: Send {}
+    fn park(&self, thread: usize, last_generation: usize) {
+        self.sleeping.fetch_add(1, Ordering::Release);
 
-        struct IsSync;
+        // Pairs with the fence in `unpark` so that either this worker observes
+        // the next generation or the broadcasting thread observes it sleeping.
+        fence(Ordering::SeqCst);
 
-        impl<T> Ambiguous<IsSync> for T where T: Sync {}
+        if self.generation.load(Ordering::Relaxed) == last_generation
+            && !self.has_task.load(Ordering::Relaxed)
+            && !self.has_target[thread - 1].load(Ordering::Relaxed)
+        {
+            #[cfg(feature = "wait_stats")]
+            self.parks.fetch_add(1, Ordering::Relaxed);
 
-        let _ = <Scope as Ambiguous<_>>::ambiguous;
+            park_thread();
+        }
+
+        self.sleeping.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn wait(&self, wait_count: &mut u32) {
+        #[cfg(feature = "wait_stats")]
+        if *wait_count < self.spin_limit {
+            self.spins.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.yields.fetch_add(1, Ordering::Relaxed);
+        }
+
+        wait(wait_count, self.spin_limit);
+    }
+
+    /// Unlike [`State::unpark`], this wakes only the given worker, regardless of whether it is sleeping.
+    pub(crate) fn unpark_worker(&self, thread: usize) {
+        if self.park
+            && let Some(thread) = self.threads[thread - 1].get()
+        {
+            thread.unpark();
+        }
+    }
+
+    pub(crate) fn unpark(&self) {
+        self.unpark_active(self.workers);
+    }
+
+    /// Like [`State::unpark`], but wakes only the first `active` workers.
+    fn unpark_active(&self, active: usize) {
+        if !self.park {
+            return;
+        }
+
+        fence(Ordering::SeqCst);
+
+        if self.sleeping.load(Ordering::Acquire) != 0 {
+            for thread in &self.threads[..active] {
+                if let Some(thread) = thread.get() {
+                    thread.unpark();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn work_span(parent: &tracing::Span, thread: usize) -> impl Drop + use<> {
+    struct WorkSpan(tracing::span::EnteredSpan, Instant);
+
+    impl Drop for WorkSpan {
+        fn drop(&mut self) {
+            self.0
+                .record("elapsed_ns", self.1.elapsed().as_nanos() as u64);
+        }
+    }
+
+    let span = tracing::debug_span!(
+        parent: parent,
+        "work",
+        thread,
+        elapsed_ns = tracing::field::Empty
+    );
+
+    WorkSpan(span.entered(), Instant::now())
+}
+
+thread_local! {
+    /// The state of the scope and the index of the thread within it of the broadcast currently running.
+    static CURRENT_THREAD: Cell<Option<(*const State, usize)>> = const { Cell::new(None) };
+}
+
+struct CurrentThread(Option<(*const State, usize)>);
+
+impl CurrentThread {
+    fn enter(state: &State, thread: usize) -> Self {
+        Self(CURRENT_THREAD.replace(Some((state, thread))))
+    }
+}
+
+impl Drop for CurrentThread {
+    fn drop(&mut self) {
+        CURRENT_THREAD.set(self.0);
+    }
+}
+
+type Work<'work> = dyn Fn(usize) + Sync + 'work;
+
+static STOP: &Work = &|_thread| ();
+
+fn wait(wait_count: &mut u32, spin_limit: u32) {
+    if *wait_count < spin_limit {
+        for _ in 0..1_u32 << *wait_count {
+            spin_loop();
+        }
+    } else {
+        yield_now();
+    }
+
+    // Workers which stay idle without parking keep yielding indefinitely.
+    *wait_count = wait_count.saturating_add(1);
+}
+
+#[cfg(all(test, feature = "std", not(loom)))]
+mod tests {
+    use super::*;
+    use std::mem::forget;
+
+    #[test]
+    fn broadcast_works() {
+        let parallelism = NonZeroUsize::new(10).unwrap();
+
+        let mut counts = (0..parallelism.get())
+            .map(|_| AtomicUsize::new(0))
+            .collect::<Vec<_>>();
+
+        scope(Some(parallelism), |scope| {
+            scope.broadcast(|thread| {
+                counts[thread].fetch_add(1, Ordering::Relaxed);
+            });
+        });
+
+        for count in &mut counts {
+            assert_eq!(*count.get_mut(), 1);
+        }
+    }
+
+    #[test]
+    fn scope_with_spin_limit_works() {
+        for spin_limit in [0, 2, 10, 31, u32::MAX] {
+            let config = Config {
+                parallelism: NonZeroUsize::new(4),
+                spin_limit,
+                ..Default::default()
+            };
+
+            let threads = scope_with(config, |scope| {
+                (0..10)
+                    .map(|_| scope.broadcast_with(|thread| thread).iter().sum::<usize>())
+                    .sum::<usize>()
+            });
+
+            assert_eq!(threads, 10 * 6);
+        }
+    }
+
+    #[test]
+    fn wait_count_saturates() {
+        let mut wait_count = u32::MAX;
+
+        wait(&mut wait_count, 16);
+
+        assert_eq!(wait_count, u32::MAX);
+    }
+
+    #[cfg(feature = "wait_stats")]
+    #[test]
+    fn wait_stats_count_yields_beyond_spin_limit() {
+        let config = Config {
+            parallelism: NonZeroUsize::new(4),
+            spin_limit: 0,
+            park: false,
+            ..Default::default()
+        };
+
+        let stats = scope_with(config, |scope| {
+            scope.broadcast(|_thread| thread::sleep(Duration::from_millis(10)));
+            thread::sleep(Duration::from_millis(10));
+
+            scope.wait_stats()
+        });
+
+        assert_eq!(stats.spins, 0);
+        assert_ne!(stats.yields, 0);
+        assert_eq!(stats.parks, 0);
+    }
+
+    #[cfg(feature = "wait_stats")]
+    #[test]
+    fn parked_workers_stop_spinning_after_idle_phases() {
+        let config = Config {
+            parallelism: NonZeroUsize::new(2),
+            spin_limit: 6,
+            park: true,
+            ..Default::default()
+        };
+
+        let idle_spins = scope_with(config, |scope| {
+            let mut idle_spins = 0;
+
+            for phase in 0..20 {
+                let before = scope.wait_stats();
+
+                // Keeps the calling thread busy until the worker has finished and started waiting again,
+                // so that only the worker's waits are counted.
+                scope.broadcast(|thread| {
+                    if thread == 0 {
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                });
+
+                thread::sleep(Duration::from_millis(5));
+
+                let after = scope.wait_stats();
+
+                // Each idle phase should make the worker start its next wait closer to parking.
+                if phase >= 10 {
+                    idle_spins += after.spins - before.spins;
+                }
+            }
+
+            idle_spins
+        });
+
+        assert_eq!(idle_spins, 0);
+    }
+
+    #[test]
+    fn on_broadcast_is_called_after_each_broadcast() {
+        let stats = Arc::new(Mutex::new(Vec::new()));
+
+        let config = Config {
+            parallelism: NonZeroUsize::new(3),
+            on_broadcast: Some(Box::new({
+                let stats = stats.clone();
+
+                move |broadcast: BroadcastStats| {
+                    stats.lock().unwrap().push((
+                        broadcast.generation,
+                        broadcast.workers,
+                        broadcast.wall_time,
+                    ));
+                }
+            })),
+            ..Default::default()
+        };
+
+        scope_with(config, |scope| {
+            scope.broadcast(|_thread| ());
+
+            scope.broadcast(|_thread| thread::sleep(Duration::from_millis(10)));
+        });
+
+        let stats = stats.lock().unwrap();
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].0, stats[0].1), (1, 2));
+        assert_eq!((stats[1].0, stats[1].1), (2, 2));
+        assert!(stats[1].2 >= Duration::from_millis(10));
+    }
+
+    #[test]
+    fn with_active_workers_restricts_dispatch() {
+        let counts = (0..4).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+
+        scope(NonZeroUsize::new(4), |scope| {
+            let active = scope.with_active_workers(1);
+
+            assert_eq!(active.parallelism(), 2);
+            assert_eq!(active.broadcast_with(|thread| thread), [0, 1]);
+
+            active.iter_static(0..10, |thread, range| {
+                counts[thread].fetch_add(range.len(), Ordering::Relaxed);
+            });
+
+            assert_eq!(scope.broadcast_with(|thread| thread), [0, 1, 2, 3]);
+        });
+
+        let counts = counts
+            .into_iter()
+            .map(AtomicUsize::into_inner)
+            .collect::<Vec<_>>();
+
+        assert_eq!(counts, [5, 5, 0, 0]);
+    }
+
+    #[test]
+    fn with_active_workers_does_not_wait_for_inactive_workers() {
+        for park in [false, true] {
+            let released = Arc::new(AtomicBool::new(false));
+
+            let config = Config {
+                parallelism: NonZeroUsize::new(4),
+                park,
+                thread_init: Some(Arc::new({
+                    let released = released.clone();
+
+                    move |thread| {
+                        // Keeps the last worker from observing any broadcast until released.
+                        while thread == 3 && !released.load(Ordering::Acquire) {
+                            thread::yield_now();
+                        }
+                    }
+                })),
+                ..Default::default()
+            };
+
+            scope_with(config, |scope| {
+                let active = scope.with_active_workers(2);
+
+                for _ in 0..3 {
+                    assert_eq!(active.broadcast_with(|thread| thread), [0, 1, 2]);
+                }
+
+                released.store(true, Ordering::Release);
+
+                assert_eq!(scope.broadcast_with(|thread| thread), [0, 1, 2, 3]);
+            });
+        }
+    }
+
+    #[test]
+    fn generation_wraps_around() {
+        for park in [false, true] {
+            let mut config = Config {
+                parallelism: NonZeroUsize::new(4),
+                park,
+                ..Default::default()
+            };
+
+            let state = State::new(&mut config).with_generation(usize::MAX - 2);
+
+            let threads = scope_with_state(&state, &config, |scope| {
+                (0..10)
+                    .map(|_| scope.broadcast_with(|thread| thread).iter().sum::<usize>())
+                    .sum::<usize>()
+            });
+
+            assert_eq!(threads, 10 * 6);
+            assert_eq!(state.generation.load(Ordering::Relaxed), 8);
+        }
+    }
+
+    #[test]
+    fn parked_workers_wake_up() {
+        let config = Config {
+            parallelism: NonZeroUsize::new(4),
+            spin_limit: 0,
+            park: true,
+            ..Default::default()
+        };
+
+        let threads = scope_with(config, |scope| {
+            (0..10)
+                .map(|_| {
+                    thread::sleep(std::time::Duration::from_millis(1));
+
+                    scope.broadcast_with(|thread| thread).iter().sum::<usize>()
+                })
+                .sum::<usize>()
+        });
+
+        assert_eq!(threads, 10 * 6);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "Miri does not support setting thread affinity")]
+    fn pinned_threads_run_on_their_cpu() {
+        let config = Config {
+            parallelism: NonZeroUsize::new(4),
+            pin_threads: true,
+            ..Default::default()
+        };
+
+        scope_with(config, |scope| {
+            assert_eq!(scope.pinned_cpu(0), None);
+            assert_eq!(scope.pinned_cpu(4), None);
+
+            #[cfg(target_os = "linux")]
+            {
+                unsafe extern "C" {
+                    fn sched_getcpu() -> i32;
+                }
+
+                let cpus = scope.broadcast_with(|_thread| unsafe { sched_getcpu() as usize });
+
+                for (thread, cpu) in cpus.into_iter().enumerate() {
+                    if let Some(pinned_cpu) = scope.pinned_cpu(thread) {
+                        assert_eq!(pinned_cpu, cpu);
+                    }
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn parallelism_matches_request() {
+        for parallelism in [1, 3, 8] {
+            scope(NonZeroUsize::new(parallelism), |scope| {
+                assert_eq!(scope.parallelism(), parallelism);
+                assert_eq!(scope.workers(), parallelism - 1);
+
+                assert_eq!(scope.broadcast_with(|thread| thread).len(), parallelism);
+            });
+        }
+    }
+
+    #[test]
+    fn in_worker_reports_current_thread() {
+        let threads = scope(NonZeroUsize::new(4), |scope| {
+            assert_eq!(scope.in_worker(), None);
+
+            let index = scope.worker_index();
+
+            let threads = scope.broadcast_with(|thread| {
+                assert_eq!(index.get(), Some(thread));
+
+                let nested = super::scope(NonZeroUsize::new(2), |nested| {
+                    let nested_index = nested.worker_index();
+
+                    nested.broadcast_with(|_thread| (nested_index.get(), index.get()))
+                });
+
+                assert_eq!(nested, [(Some(0), None), (Some(1), None)]);
+
+                index.get()
+            });
+
+            super::scope(NonZeroUsize::new(2), |unrelated| {
+                assert_eq!(unrelated.in_worker(), None);
+
+                unrelated.broadcast(|_thread| assert_eq!(index.get(), None));
+            });
+
+            assert_eq!(scope.in_worker(), None);
+
+            threads
+        });
+
+        assert_eq!(threads, [Some(0), Some(1), Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn worker_threads_use_name_prefix() {
+        let config = Config {
+            parallelism: NonZeroUsize::new(3),
+            thread_name_prefix: "myphase".to_owned(),
+            ..Default::default()
+        };
+
+        let names = scope_with(config, |scope| {
+            scope.broadcast_with(|_thread| thread::current().name().map(ToOwned::to_owned))
+        });
+
+        assert_eq!(names[1].as_deref(), Some("myphase-worker-1"));
+        assert_eq!(names[2].as_deref(), Some("myphase-worker-2"));
+
+        let names = scope(NonZeroUsize::new(2), |scope| {
+            scope.broadcast_with(|_thread| thread::current().name().map(ToOwned::to_owned))
+        });
+
+        assert_eq!(names[1].as_deref(), Some("fork-join-scope-worker-1"));
+    }
+
+    #[test]
+    fn worker_threads_use_stack_size() {
+        fn recurse(depth: usize) -> usize {
+            let frame = std::hint::black_box([1_u8; 1024]);
+
+            if depth == 0 {
+                frame[0] as usize
+            } else {
+                recurse(depth - 1) + frame[1] as usize
+            }
+        }
+
+        let config = Config {
+            parallelism: NonZeroUsize::new(3),
+            stack_size: Some(16 * 1024 * 1024),
+            ..Default::default()
+        };
+
+        let depths = scope_with(config, |scope| {
+            scope.broadcast_with(|thread| if thread != 0 { recurse(4 * 1024) } else { 0 })
+        });
+
+        assert_eq!(depths, [0, 4 * 1024 + 1, 4 * 1024 + 1]);
+    }
+
+    #[test]
+    fn broadcast_with_works() {
+        let parallelism = NonZeroUsize::new(10).unwrap();
+
+        let threads = scope(Some(parallelism), |scope| {
+            scope.broadcast_with(|thread| thread.to_string())
+        });
+
+        assert_eq!(threads.len(), parallelism.get());
+
+        for (index, thread) in threads.into_iter().enumerate() {
+            assert_eq!(thread, index.to_string());
+        }
+    }
+
+    #[test]
+    fn try_broadcast_with_returns_first_error() {
+        scope(NonZeroUsize::new(4), |scope| {
+            assert_eq!(
+                scope.try_broadcast_with(Ok::<_, usize>),
+                Ok(vec![0, 1, 2, 3])
+            );
+
+            assert_eq!(
+                scope.try_broadcast_with(|thread| if thread >= 2 { Err(thread) } else { Ok(()) }),
+                Err(2)
+            );
+
+            assert_eq!(scope.try_broadcast_with(Err::<(), _>), Err(0));
+        });
+    }
+
+    #[test]
+    fn worker_panic_propagates() {
+        let parallelism = NonZeroUsize::new(4).unwrap();
+
+        let panic = catch_unwind(|| {
+            scope(Some(parallelism), |scope| {
+                scope.broadcast(|thread| {
+                    if thread == 2 {
+                        panic!("worker panicked");
+                    }
+                });
+            });
+        })
+        .unwrap_err();
+
+        assert_eq!(*panic.downcast::<&str>().unwrap(), "worker panicked");
+    }
+
+    #[test]
+    fn unwinding_scope_finishes_in_flight_broadcast() {
+        let finished = AtomicUsize::new(0);
+
+        let panic = catch_unwind(AssertUnwindSafe(|| {
+            scope(NonZeroUsize::new(4), |scope| {
+                scope.broadcast(|thread| {
+                    if thread == 0 {
+                        panic!("caller panicked");
+                    }
+
+                    thread::sleep(Duration::from_millis(10));
+
+                    finished.fetch_add(1, Ordering::Relaxed);
+                });
+            });
+        }))
+        .unwrap_err();
+
+        assert_eq!(*panic.downcast::<&str>().unwrap(), "caller panicked");
+        assert_eq!(finished.into_inner(), 3);
+    }
+
+    #[test]
+    fn thread_init_and_deinit_run_once_per_worker() {
+        let inits = Arc::new(Mutex::new(Vec::new()));
+        let deinits = Arc::new(Mutex::new(Vec::new()));
+
+        let config = Config {
+            parallelism: NonZeroUsize::new(4),
+            thread_init: Some(Arc::new({
+                let inits = inits.clone();
+
+                move |thread| inits.lock().unwrap().push(thread)
+            })),
+            thread_deinit: Some(Arc::new({
+                let deinits = deinits.clone();
+
+                move |thread| deinits.lock().unwrap().push(thread)
+            })),
+            ..Default::default()
+        };
+
+        scope_with(config, |scope| {
+            for _ in 0..3 {
+                scope.broadcast(|_thread| ());
+            }
+
+            let mut inits = inits.lock().unwrap().clone();
+            inits.sort_unstable();
+            assert_eq!(inits, [1, 2, 3]);
+
+            assert!(deinits.lock().unwrap().is_empty());
+        });
+
+        let mut deinits = deinits.lock().unwrap().clone();
+        deinits.sort_unstable();
+        assert_eq!(deinits, [1, 2, 3]);
+    }
+
+    #[test]
+    fn broadcast_timeout_keeps_waiting_for_slow_workers() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+
+        let config = Config {
+            parallelism: NonZeroUsize::new(3),
+            broadcast_timeout: Some(Duration::from_millis(5)),
+            on_broadcast_timeout: Some(Box::new({
+                let reports = reports.clone();
+
+                move |timeout: BroadcastTimeout| {
+                    reports.lock().unwrap().push((
+                        timeout.generation,
+                        timeout.elapsed,
+                        timeout.pending,
+                    ));
+                }
+            })),
+            ..Default::default()
+        };
+
+        let threads = scope_with(config, |scope| {
+            scope.broadcast_with(|thread| {
+                if thread == 2 {
+                    thread::sleep(Duration::from_millis(50));
+                }
+
+                thread
+            })
+        });
+
+        assert_eq!(threads, [0, 1, 2]);
+
+        let reports = reports.lock().unwrap();
+
+        assert!(!reports.is_empty());
+
+        for (generation, elapsed, pending) in reports.iter() {
+            assert_eq!(*generation, 1);
+            assert!(*elapsed >= Duration::from_millis(5));
+            assert_eq!(*pending, [2]);
+        }
+    }
+
+    #[test]
+    fn broadcast_timeout_stops_reporting_on_overflow() {
+        let state = State::new(&mut Config {
+            parallelism: NonZeroUsize::new(2),
+            on_broadcast_timeout: Some(Box::new(|_timeout| {
+                panic!("reported an unreachable deadline")
+            })),
+            ..Default::default()
+        });
+
+        let mut reported = 1;
+
+        state.report_timeout(1, 1, Duration::MAX, Instant::now(), &mut reported);
+
+        reported = u32::MAX;
+
+        state.report_timeout(1, 1, Duration::from_nanos(1), Instant::now(), &mut reported);
+    }
+
+    #[test]
+    fn deferred_broadcast_overlaps_with_caller() {
+        for parallelism in [1, 4] {
+            let counts = Arc::new(
+                (0..parallelism)
+                    .map(|_| AtomicUsize::new(0))
+                    .collect::<Vec<_>>(),
+            );
+
+            let overlapped = scope(NonZeroUsize::new(parallelism), |scope| {
+                let guard = scope.broadcast_deferred({
+                    let counts = counts.clone();
+
+                    move |thread| {
+                        counts[thread].fetch_add(1, Ordering::Relaxed);
+                    }
+                });
+
+                let overlapped = counts[0].load(Ordering::Relaxed);
+
+                guard.wait();
+
+                drop(scope.broadcast_deferred({
+                    let counts = counts.clone();
+
+                    move |thread| {
+                        counts[thread].fetch_add(1, Ordering::Relaxed);
+                    }
+                }));
+
+                overlapped
+            });
+
+            assert_eq!(overlapped, 0);
+
+            for count in counts.iter() {
+                assert_eq!(count.load(Ordering::Relaxed), 2);
+            }
+        }
+    }
+
+    #[test]
+    fn leaked_deferred_broadcast_blocks_further_broadcasts() {
+        let panic = catch_unwind(|| {
+            scope(NonZeroUsize::new(2), |scope| {
+                forget(scope.broadcast_deferred(|_thread| ()));
+
+                scope.broadcast(|_thread| ());
+            });
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            *panic.downcast::<&str>().unwrap(),
+            "broadcast must not be called from within a broadcast of the same scope"
+        );
+    }
+
+    #[test]
+    fn caller_panic_tears_down_cleanly() {
+        let parallelism = NonZeroUsize::new(4).unwrap();
+
+        let mut counts = (0..parallelism.get())
+            .map(|_| AtomicUsize::new(0))
+            .collect::<Vec<_>>();
+
+        let panic = catch_unwind(AssertUnwindSafe(|| {
+            scope(Some(parallelism), |scope| {
+                let panic = catch_unwind(AssertUnwindSafe(|| {
+                    scope.broadcast(|thread| match thread {
+                        0 => panic!("caller panicked"),
+                        2 => panic!("worker panicked"),
+                        _ => (),
+                    });
+                }))
+                .unwrap_err();
+
+                assert_eq!(*panic.downcast::<&str>().unwrap(), "caller panicked");
+
+                scope.broadcast(|thread| {
+                    counts[thread].fetch_add(1, Ordering::Relaxed);
+                });
+
+                scope.broadcast(|thread| {
+                    if thread == 0 {
+                        panic!("caller panicked again");
+                    }
+                });
+            });
+        }))
+        .unwrap_err();
+
+        assert_eq!(*panic.downcast::<&str>().unwrap(), "caller panicked again");
+
+        for count in &mut counts {
+            assert_eq!(*count.get_mut(), 1);
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn broadcast_emits_spans() {
+        use std::sync::atomic::AtomicU64;
+
+        use tracing::{
+            Event, Metadata, Subscriber,
+            span::{Attributes, Id, Record},
+        };
+
+        static BROADCASTS: AtomicUsize = AtomicUsize::new(0);
+        static WORKS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counter(AtomicU64);
+
+        impl Subscriber for Counter {
+            fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                match span.metadata().name() {
+                    "broadcast" => BROADCASTS.fetch_add(1, Ordering::Relaxed),
+                    "work" => WORKS.fetch_add(1, Ordering::Relaxed),
+                    _ => 0,
+                };
+
+                Id::from_u64(self.0.fetch_add(1, Ordering::Relaxed))
+            }
+
+            fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+            fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+            fn event(&self, _event: &Event<'_>) {}
+
+            fn enter(&self, _span: &Id) {}
+
+            fn exit(&self, _span: &Id) {}
+        }
+
+        tracing::subscriber::set_global_default(Counter(AtomicU64::new(1))).unwrap();
+
+        scope(NonZeroUsize::new(4), |scope| {
+            for _ in 0..10 {
+                scope.broadcast(|_thread| ());
+            }
+        });
+
+        assert!(BROADCASTS.load(Ordering::Relaxed) >= 10);
+        assert!(WORKS.load(Ordering::Relaxed) >= 10 * 4);
+    }
+
+    #[test]
+    fn catch_broadcast_with_retains_results() {
+        scope(NonZeroUsize::new(4), |scope| {
+            let results = scope.catch_broadcast_with(|thread| thread.to_string());
+
+            assert_eq!(results.ok().unwrap(), ["0", "1", "2", "3"]);
+
+            let partial = scope
+                .catch_broadcast_with(|thread| {
+                    if thread == 2 {
+                        panic!("worker panicked");
+                    }
+
+                    thread.to_string()
+                })
+                .err()
+                .unwrap();
+
+            assert_eq!(
+                partial.results,
+                [
+                    Some("0".to_owned()),
+                    Some("1".to_owned()),
+                    None,
+                    Some("3".to_owned())
+                ]
+            );
+            assert_eq!(
+                *partial.panic.downcast::<&str>().unwrap(),
+                "worker panicked"
+            );
+        });
+    }
+
+    #[test]
+    fn reentrant_broadcast_panics() {
+        use crate::Pool;
+
+        thread_local! {
+            static POOL: Pool = Pool::new(Config {
+                parallelism: NonZeroUsize::new(3),
+                ..Default::default()
+            });
+        }
+
+        let counts = (0..3).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>();
+
+        POOL.with(|pool| {
+            pool.scope(|scope| {
+                scope.broadcast(|thread| {
+                    if thread == 0 {
+                        let panic = catch_unwind(|| {
+                            POOL.with(|pool| pool.scope(|scope| scope.broadcast(|_thread| ())));
+                        })
+                        .unwrap_err();
+
+                        assert_eq!(
+                            *panic.downcast::<&str>().unwrap(),
+                            "broadcast must not be called from within a broadcast of the same scope"
+                        );
+                    }
+
+                    counts[thread].fetch_add(1, Ordering::Relaxed);
+                });
+
+                scope.broadcast(|thread| {
+                    counts[thread].fetch_add(1, Ordering::Relaxed);
+                });
+            });
+        });
+
+        for count in counts {
+            assert_eq!(count.into_inner(), 2);
+        }
+    }
+
+    #[test]
+    fn scope_is_neither_send_nor_sync() {
+        trait Ambiguous<A> {
+            fn ambiguous() {}
+        }
+
+        impl<T> Ambiguous<()> for T {}
+
+        struct IsSend;
+
+        impl<T> Ambiguous<IsSend> for T where T: Send {}
+
+        struct IsSync;
+
+        impl<T> Ambiguous<IsSync> for T where T: Sync {}
+
+        let _ = <Scope as Ambiguous<_>>::ambiguous;
+    }
+}
+
+/// Run using `RUSTFLAGS="--cfg loom" cargo test --release --lib loom_tests`.
+///
+/// The workers park instead of yielding as two threads spinning against each other
+/// would keep the model from making progress.
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+
+    use loom::thread;
+
+    fn model<F>(f: F)
+    where
+        F: Fn(&State) + Sync + Send + 'static,
+    {
+        let mut builder = loom::model::Builder::new();
+        builder.preemption_bound = Some(2);
+
+        builder.check(move || {
+            let mut config = Config {
+                parallelism: NonZeroUsize::new(3),
+                spin_limit: 0,
+                park: true,
+                ..Default::default()
+            };
+
+            let state = Arc::new(State::new(&mut config));
+
+            let workers = (1..=2)
+                .map(|thread| {
+                    let state = state.clone();
+
+                    thread::spawn(move || state.worker(thread))
+                })
+                .collect::<Vec<_>>();
+
+            f(&state);
+
+            state.stop();
+
+            for worker in workers {
+                worker.join().unwrap();
+            }
+        });
+    }
+
+    #[test]
+    fn broadcast_reaches_both_workers() {
+        model(|state| {
+            let counts = [0, 1, 2].map(|_| AtomicUsize::new(0));
+
+            Scope::new(state).broadcast(|thread| {
+                counts[thread].fetch_add(1, Ordering::Relaxed);
+            });
+
+            for count in &counts {
+                assert_eq!(count.load(Ordering::Relaxed), 1);
+            }
+        });
+    }
+
+    #[test]
+    fn consecutive_broadcasts_do_not_overlap() {
+        model(|state| {
+            let scope = Scope::new(state);
+
+            let count = AtomicUsize::new(0);
+
+            scope.broadcast(|_thread| {
+                count.fetch_add(1, Ordering::Relaxed);
+            });
+
+            scope.broadcast(|_thread| {
+                assert_eq!(count.load(Ordering::Relaxed), 3);
+            });
+        });
     }
 }
@@ -0,0 +1,877 @@
+use std::mem::MaybeUninit;
+use std::ops::Range;
+use std::{ptr, slice};
+
+use crate::{Aligned, Synced, scope::Scope};
+
+impl Scope<'_> {
+    pub fn collect<T, F>(&self, work: Range<usize>, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(usize) -> T + Sync,
+    {
+        let (base, len) = (work.start, work.len());
+
+        let mut output = Vec::<T>::with_capacity(len);
+        let output_ptr = Synced(output.as_mut_ptr());
+
+        self.iter_static(work, |_thread, range| {
+            let output_ptr = output_ptr;
+
+            for index in range {
+                unsafe {
+                    output_ptr.0.add(index - base).write(f(index));
+                }
+            }
+        });
+
+        // SAFETY: The ranges passed to the threads cover `work` completely, so all elements are
+        // initialized if `iter_static` returns normally. If any invocation of `f` panics,
+        // the panic is propagated before reaching this point and the elements are leaked.
+        unsafe {
+            output.set_len(len);
+        }
+
+        output
+    }
+
+    /// Appends `additional` elements to `vec` which are initialized in place using [`Scope::init_static`].
+    ///
+    /// `f` is passed the offset of each chunk relative to the old length and must return the chunk itself
+    /// after initializing it, e.g. using [`MaybeUninit::write`] followed by `assume_init_mut`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` does not return exactly the chunk it was passed.
+    pub fn extend_static<T, F>(&self, vec: &mut Vec<T>, additional: usize, f: F)
+    where
+        T: Send,
+        F: for<'a> Fn(usize, &'a mut [MaybeUninit<T>]) -> &'a mut [T] + Sync,
+    {
+        let len = vec.len();
+
+        vec.reserve(additional);
+
+        self.init_static(
+            &mut vec.spare_capacity_mut()[..additional],
+            |offset, chunk| {
+                let (chunk_ptr, chunk_len) = (chunk.as_ptr().cast::<T>(), chunk.len());
+
+                let init = f(offset, chunk);
+
+                // Producing the returned slice already required `f` to assert that the chunk is initialized.
+                assert!(
+                    ptr::eq(init.as_ptr(), chunk_ptr) && init.len() == chunk_len,
+                    "chunk at offset {offset} was not returned after initialization"
+                );
+            },
+        );
+
+        // SAFETY: The chunks cover the first `additional` elements of the spare capacity,
+        // all of which were returned by `f` as initialized. If `f` or the check panics,
+        // the panic is propagated before reaching this point and the elements are leaked.
+        unsafe {
+            vec.set_len(len + additional);
+        }
+    }
+
+    /// Materializes `iter` into a vector before processing it using [`Scope::for_each_static`],
+    /// so it is not suitable for infinite or streaming sources.
+    pub fn for_each_collect<I, T, F>(&self, iter: I, f: F) -> Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+        T: Send,
+        F: Fn(&mut [T]) + Sync,
+    {
+        let mut data = iter.into_iter().collect::<Vec<_>>();
+
+        self.for_each_static(&mut data, f);
+
+        data
+    }
+
+    /// Thread 0 fills its share using `value` itself while the workers use a clone each.
+    ///
+    /// Like [`slice::fill`], each thread moves its value into the last element of its share,
+    /// so `value` is cloned `data.len() - 1` times if every thread gets at least one element.
+    pub fn fill<T>(&self, data: &mut [T], value: T)
+    where
+        T: Clone + Send,
+    {
+        let mut values = (0..=self.workers)
+            .map(|thread| Aligned((thread != 0).then(|| value.clone())))
+            .collect::<Vec<_>>();
+
+        *values[0] = Some(value);
+
+        let values_ptr = Synced(values.as_mut_ptr());
+
+        let data_ptr = Synced(data.as_mut_ptr());
+
+        self.iter_static(0..data.len(), |thread, range| {
+            let data_ptr = data_ptr;
+            let values_ptr = values_ptr;
+
+            let data =
+                unsafe { slice::from_raw_parts_mut(data_ptr.0.add(range.start), range.len()) };
+            let value = unsafe { &mut *values_ptr.0.add(thread) };
+
+            data.fill(value.take().unwrap());
+        });
+    }
+
+    pub fn copy_from_slice<T>(&self, dst: &mut [T], src: &[T])
+    where
+        T: Copy + Send + Sync,
+    {
+        assert_eq!(dst.len(), src.len());
+
+        self.for_each_static_indexed(dst, |range, dst| {
+            dst.copy_from_slice(&src[range]);
+        });
+    }
+
+    /// Rotates `data` by first moving it into a scratch buffer of the same size in parallel.
+    pub fn rotate_left<T>(&self, data: &mut [T], mid: usize)
+    where
+        T: Send,
+    {
+        assert!(mid <= data.len());
+
+        let len = data.len();
+
+        let mut scratch = Vec::<T>::with_capacity(len);
+
+        let data_ptr = Synced(data.as_mut_ptr());
+        let scratch_ptr = Synced(scratch.as_mut_ptr());
+
+        // SAFETY: Each element is moved into exactly one slot of `scratch` and back again
+        // without running any user code in between, so `data` is fully initialized when we return.
+        self.iter_static(0..len, |_thread, range| {
+            let data_ptr = data_ptr;
+            let scratch_ptr = scratch_ptr;
+
+            for index in range {
+                let target = if index < mid {
+                    index + (len - mid)
+                } else {
+                    index - mid
+                };
+
+                unsafe {
+                    data_ptr
+                        .0
+                        .add(index)
+                        .copy_to_nonoverlapping(scratch_ptr.0.add(target), 1);
+                }
+            }
+        });
+
+        self.iter_static(0..len, |_thread, range| {
+            let data_ptr = data_ptr;
+            let scratch_ptr = scratch_ptr;
+
+            unsafe {
+                scratch_ptr
+                    .0
+                    .add(range.start)
+                    .copy_to_nonoverlapping(data_ptr.0.add(range.start), range.len());
+            }
+        });
+    }
+
+    pub fn fill_with<T, F>(&self, data: &mut [T], f: F)
+    where
+        T: Send,
+        F: Fn(usize) -> T + Sync,
+    {
+        self.for_each_static_indexed(data, |range, data| {
+            for (index, value) in range.zip(data) {
+                *value = f(index);
+            }
+        });
+    }
+
+    /// Concatenates the outputs of all threads in thread order, i.e. in input order.
+    pub fn flat_map<T, U, F>(&self, input: &[T], f: F) -> Vec<U>
+    where
+        T: Sync,
+        U: Send,
+        F: Fn(&T, &mut Vec<U>) + Sync,
+    {
+        let mut outputs = (0..=self.workers)
+            .map(|_| Aligned(Vec::new()))
+            .collect::<Vec<_>>();
+
+        let outputs_ptr = Synced(outputs.as_mut_ptr());
+
+        self.iter_static(0..input.len(), |thread, range| {
+            let outputs_ptr = outputs_ptr;
+
+            let output = unsafe { &mut *outputs_ptr.0.add(thread) };
+
+            for value in &input[range] {
+                f(value, output);
+            }
+        });
+
+        let len = outputs.iter().map(|output| output.len()).sum();
+        let mut output = Vec::with_capacity(len);
+
+        for mut chunk in outputs {
+            output.append(&mut chunk);
+        }
+
+        output
+    }
+
+    /// Splits `input` into the elements which do and do not satisfy `pred`, preserving their order.
+    ///
+    /// Each thread clones its elements into local buffers which are then concatenated,
+    /// so peak memory usage is about twice the size of `input` in addition to `input` itself.
+    pub fn partition<T, P>(&self, input: &[T], pred: P) -> (Vec<T>, Vec<T>)
+    where
+        T: Clone + Send + Sync,
+        P: Fn(&T) -> bool + Sync,
+    {
+        let mut outputs = (0..=self.workers)
+            .map(|_| Aligned((Vec::new(), Vec::new())))
+            .collect::<Vec<_>>();
+
+        let outputs_ptr = Synced(outputs.as_mut_ptr());
+
+        self.iter_static(0..input.len(), |thread, range| {
+            let outputs_ptr = outputs_ptr;
+
+            let (matching, rest) = unsafe { &mut **outputs_ptr.0.add(thread) };
+
+            for value in &input[range] {
+                if pred(value) {
+                    matching.push(value.clone());
+                } else {
+                    rest.push(value.clone());
+                }
+            }
+        });
+
+        let matching_len = outputs
+            .iter()
+            .map(|Aligned((matching, _))| matching.len())
+            .sum();
+        let rest_len = outputs.iter().map(|Aligned((_, rest))| rest.len()).sum();
+
+        let mut matching = Vec::with_capacity(matching_len);
+        let mut rest = Vec::with_capacity(rest_len);
+
+        for Aligned((mut matching_chunk, mut rest_chunk)) in outputs {
+            matching.append(&mut matching_chunk);
+            rest.append(&mut rest_chunk);
+        }
+
+        (matching, rest)
+    }
+
+    /// Removes consecutive duplicates by moving the retained elements to the front of `data`,
+    /// preserving their order, and returns their number. The order of the remaining elements is unspecified.
+    ///
+    /// Each thread deduplicates its own chunk before the chunks are fixed up at their boundaries.
+    /// Like [`Scope::retain`], the elements of all chunks are then moved into a scratch buffer
+    /// of the same size as `data` and back again in parallel.
+    pub fn dedup<T>(&self, data: &mut [T]) -> usize
+    where
+        T: PartialEq + Send,
+    {
+        let len = data.len();
+
+        let mut retained = (0..=self.workers)
+            .map(|_| Aligned(0..0))
+            .collect::<Vec<_>>();
+
+        let data_ptr = Synced(data.as_mut_ptr());
+        let retained_ptr = Synced(retained.as_mut_ptr());
+
+        self.iter_static(0..len, |thread, range| {
+            let data_ptr = data_ptr;
+            let retained_ptr = retained_ptr;
+
+            let data =
+                unsafe { slice::from_raw_parts_mut(data_ptr.0.add(range.start), range.len()) };
+            let retained = unsafe { &mut *retained_ptr.0.add(thread) };
+
+            let mut len = 0;
+
+            for index in 0..data.len() {
+                if len == 0 || data[len - 1] != data[index] {
+                    data.swap(len, index);
+                    len += 1;
+                }
+            }
+
+            **retained = range.start..range.start + len;
+        });
+
+        // As each chunk is already deduplicated, at most its first retained element
+        // can be a duplicate of the last element retained by any of the preceding chunks.
+        let mut last = None;
+
+        for range in &mut retained {
+            if let Some(last) = last
+                && !range.is_empty()
+                && data[last] == data[range.start]
+            {
+                range.start += 1;
+            }
+
+            if !range.is_empty() {
+                last = Some(range.end - 1);
+            }
+        }
+
+        let mut offsets = Vec::with_capacity(retained.len());
+        let mut kept = 0;
+
+        for range in &retained {
+            offsets.push(kept);
+
+            kept += range.len();
+        }
+
+        let mut scratch = Vec::<T>::with_capacity(len);
+        let scratch_ptr = Synced(scratch.as_mut_ptr());
+
+        // SAFETY: The retained ranges and the offsets partition the indices of `data` into the disjoint
+        // target ranges of each thread in `scratch`, so every element is moved to exactly one slot.
+        // No user code runs from here on, so `data` is fully initialized again when we return.
+        self.iter_static(0..len, |thread, range| {
+            let data_ptr = data_ptr;
+            let scratch_ptr = scratch_ptr;
+
+            let retained = &*retained[thread];
+
+            // All elements of the preceding chunks which were not retained are moved behind the retained ones.
+            let mut kept_target = offsets[thread];
+            let mut rejected_target = kept + range.start - offsets[thread];
+
+            for index in range {
+                let target = if retained.contains(&index) {
+                    &mut kept_target
+                } else {
+                    &mut rejected_target
+                };
+
+                unsafe {
+                    data_ptr
+                        .0
+                        .add(index)
+                        .copy_to_nonoverlapping(scratch_ptr.0.add(*target), 1);
+                }
+
+                *target += 1;
+            }
+        });
+
+        self.iter_static(0..len, |_thread, range| {
+            let data_ptr = data_ptr;
+            let scratch_ptr = scratch_ptr;
+
+            unsafe {
+                scratch_ptr
+                    .0
+                    .add(range.start)
+                    .copy_to_nonoverlapping(data_ptr.0.add(range.start), range.len());
+            }
+        });
+
+        kept
+    }
+
+    /// Moves the elements which satisfy `pred` to the front of `data`, preserving their order,
+    /// and returns their number. The remaining elements are moved to the back.
+    ///
+    /// Each thread evaluates `pred` for its chunk before the elements of all chunks are moved
+    /// into a scratch buffer of the same size as `data` and back again in parallel.
+    pub fn retain<T, P>(&self, data: &mut [T], pred: P) -> usize
+    where
+        T: Send,
+        P: Fn(&T) -> bool + Sync,
+    {
+        let len = data.len();
+
+        let mut keep = vec![false; len];
+        let mut counts = (0..=self.workers)
+            .map(|_| Aligned((0, 0)))
+            .collect::<Vec<_>>();
+
+        let data_ptr = Synced(data.as_mut_ptr());
+        let keep_ptr = Synced(keep.as_mut_ptr());
+        let counts_ptr = Synced(counts.as_mut_ptr());
+
+        self.iter_static(0..len, |thread, range| {
+            let data_ptr = data_ptr;
+            let keep_ptr = keep_ptr;
+            let counts_ptr = counts_ptr;
+
+            let data = unsafe { slice::from_raw_parts(data_ptr.0.add(range.start), range.len()) };
+            let keep =
+                unsafe { slice::from_raw_parts_mut(keep_ptr.0.add(range.start), range.len()) };
+            let count = unsafe { &mut *counts_ptr.0.add(thread) };
+
+            let mut kept = 0;
+
+            for (value, keep) in data.iter().zip(keep) {
+                *keep = pred(value);
+                kept += *keep as usize;
+            }
+
+            **count = (kept, range.len() - kept);
+        });
+
+        let retained = counts.iter().map(|count| count.0.0).sum::<usize>();
+
+        let mut kept_offset = 0;
+        let mut rejected_offset = retained;
+
+        for count in &mut counts {
+            let (kept, rejected) = **count;
+
+            **count = (kept_offset, rejected_offset);
+
+            kept_offset += kept;
+            rejected_offset += rejected;
+        }
+
+        let mut scratch = Vec::<T>::with_capacity(len);
+        let scratch_ptr = Synced(scratch.as_mut_ptr());
+
+        // SAFETY: `keep` partitions the indices of `data` into the disjoint target ranges
+        // of each thread in `scratch`, so every element is moved to exactly one slot.
+        // No user code runs from here on, so `data` is fully initialized again when we return.
+        self.iter_static(0..len, |thread, range| {
+            let data_ptr = data_ptr;
+            let scratch_ptr = scratch_ptr;
+            let counts_ptr = counts_ptr;
+
+            let (mut kept, mut rejected) = unsafe { **counts_ptr.0.add(thread) };
+
+            for index in range {
+                let target = if keep[index] {
+                    &mut kept
+                } else {
+                    &mut rejected
+                };
+
+                unsafe {
+                    data_ptr
+                        .0
+                        .add(index)
+                        .copy_to_nonoverlapping(scratch_ptr.0.add(*target), 1);
+                }
+
+                *target += 1;
+            }
+        });
+
+        self.iter_static(0..len, |_thread, range| {
+            let data_ptr = data_ptr;
+            let scratch_ptr = scratch_ptr;
+
+            unsafe {
+                scratch_ptr
+                    .0
+                    .add(range.start)
+                    .copy_to_nonoverlapping(data_ptr.0.add(range.start), range.len());
+            }
+        });
+
+        retained
+    }
+
+    /// Computes an inclusive scan of `data` using the associative operation `f`.
+    ///
+    /// Each thread first scans its own chunk, then the chunk totals are scanned sequentially
+    /// and finally each thread combines the total of all preceding chunks into its own chunk.
+    pub fn scan_inplace<T, F>(&self, data: &mut [T], f: F)
+    where
+        T: Send + Clone,
+        F: Fn(&T, &T) -> T + Sync,
+    {
+        let mut carries = (0..=self.workers)
+            .map(|_| Aligned(None))
+            .collect::<Vec<_>>();
+
+        let data_ptr = Synced(data.as_mut_ptr());
+        let carries_ptr = Synced(carries.as_mut_ptr());
+
+        self.iter_static(0..data.len(), |thread, range| {
+            let data_ptr = data_ptr;
+            let carries_ptr = carries_ptr;
+
+            let data =
+                unsafe { slice::from_raw_parts_mut(data_ptr.0.add(range.start), range.len()) };
+            let carry = unsafe { &mut *carries_ptr.0.add(thread) };
+
+            for index in 1..data.len() {
+                data[index] = f(&data[index - 1], &data[index]);
+            }
+
+            **carry = data.last().cloned();
+        });
+
+        let mut total = None::<T>;
+
+        for carry in &mut carries {
+            let Some(chunk_total) = carry.take() else {
+                continue;
+            };
+
+            **carry = total.clone();
+
+            total = Some(match total {
+                Some(total) => f(&total, &chunk_total),
+                None => chunk_total,
+            });
+        }
+
+        self.iter_static(0..data.len(), |thread, range| {
+            let data_ptr = data_ptr;
+            let carries_ptr = carries_ptr;
+
+            let data =
+                unsafe { slice::from_raw_parts_mut(data_ptr.0.add(range.start), range.len()) };
+            let carry = unsafe { &*carries_ptr.0.add(thread) };
+
+            if let Some(carry) = &**carry {
+                for value in data {
+                    *value = f(carry, value);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::collections::{BTreeSet, HashMap};
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::scope::scope;
+
+    #[test]
+    fn collect_works() {
+        for length in [0, 1, 1_000] {
+            let strings = scope(NonZeroUsize::new(3), |scope| {
+                scope.collect(5..5 + length, |index| index.to_string())
+            });
+
+            assert_eq!(strings.len(), length);
+
+            for (index, string) in strings.into_iter().enumerate() {
+                assert_eq!(string, (5 + index).to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn extend_static_initializes_in_place() {
+        for additional in [0, 1, 1_000] {
+            let mut strings = vec!["first".to_owned()];
+
+            scope(NonZeroUsize::new(3), |scope| {
+                scope.extend_static(&mut strings, additional, |offset, chunk| {
+                    for (index, string) in chunk.iter_mut().enumerate() {
+                        string.write((offset + index).to_string());
+                    }
+
+                    unsafe { chunk.assume_init_mut() }
+                });
+            });
+
+            assert_eq!(strings.len(), 1 + additional);
+            assert_eq!(strings[0], "first");
+
+            for (index, string) in strings[1..].iter().enumerate() {
+                assert_eq!(*string, index.to_string());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic = "was not returned after initialization"]
+    fn extend_static_checks_returned_chunks() {
+        let mut nums = Vec::<usize>::new();
+
+        scope(NonZeroUsize::new(3), |scope| {
+            scope.extend_static(&mut nums, 10, |_offset, chunk| unsafe {
+                chunk[..0].assume_init_mut()
+            });
+        });
+    }
+
+    #[test]
+    fn for_each_collect_works() {
+        let map = (0..1_000)
+            .map(|num| (num, num * 2))
+            .collect::<HashMap<_, _>>();
+        let set = (0..1_000).collect::<BTreeSet<usize>>();
+
+        scope(NonZeroUsize::new(3), |scope| {
+            let mut values = scope.for_each_collect(map.into_values(), |values| {
+                for value in values {
+                    *value += 1;
+                }
+            });
+
+            values.sort_unstable();
+
+            assert_eq!(
+                values,
+                (0..1_000).map(|num| 2 * num + 1).collect::<Vec<_>>()
+            );
+
+            let strings =
+                scope.for_each_collect(set.iter().map(|num| num.to_string()), |strings| {
+                    for string in strings {
+                        string.push('!');
+                    }
+                });
+
+            assert_eq!(
+                strings,
+                set.iter().map(|num| format!("{num}!")).collect::<Vec<_>>()
+            );
+        });
+    }
+
+    #[test]
+    fn copy_from_slice_works() {
+        for length in [0, 1, 1_000] {
+            let src = (0..length).collect::<Vec<usize>>();
+            let mut dst = vec![0; length];
+
+            scope(NonZeroUsize::new(3), |scope| {
+                scope.copy_from_slice(&mut dst, &src)
+            });
+
+            assert_eq!(dst, src);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn copy_from_slice_rejects_length_mismatch() {
+        scope(NonZeroUsize::new(3), |scope| {
+            scope.copy_from_slice(&mut [0; 3], &[1, 2])
+        });
+    }
+
+    #[test]
+    fn rotate_left_works() {
+        for length in [0, 1, 10, 1_000] {
+            for mid in [0, length / 3, length] {
+                let mut strings = (0..length).map(|num| num.to_string()).collect::<Vec<_>>();
+
+                let mut expected = strings.clone();
+                expected.rotate_left(mid);
+
+                scope(NonZeroUsize::new(3), |scope| {
+                    scope.rotate_left(&mut strings, mid)
+                });
+
+                assert_eq!(strings, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn fill_works() {
+        struct CountClones<'a>(&'a AtomicUsize);
+
+        impl Clone for CountClones<'_> {
+            fn clone(&self) -> Self {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                Self(self.0)
+            }
+        }
+
+        for length in [0, 1, 1_000] {
+            let mut strings = vec![String::new(); length];
+
+            scope(NonZeroUsize::new(3), |scope| {
+                scope.fill(&mut strings, "foo".to_owned());
+            });
+
+            assert!(strings.iter().all(|string| string == "foo"));
+
+            let clones = AtomicUsize::new(0);
+
+            let mut values = (0..length)
+                .map(|_| CountClones(&clones))
+                .collect::<Vec<_>>();
+
+            scope(NonZeroUsize::new(3), |scope| {
+                scope.fill(&mut values, CountClones(&clones));
+            });
+
+            // Without any elements for the workers, only their clones are made.
+            assert_eq!(clones.into_inner(), length.saturating_sub(1).max(2));
+
+            scope(NonZeroUsize::new(3), |scope| {
+                scope.fill_with(&mut strings, |index| index.to_string());
+            });
+
+            for (index, string) in strings.into_iter().enumerate() {
+                assert_eq!(string, index.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn flat_map_works() {
+        let nums = (0..1_000).collect::<Vec<usize>>();
+
+        let expected = nums
+            .iter()
+            .flat_map(|num| vec![*num; num % 3])
+            .collect::<Vec<_>>();
+
+        for parallelism in [1, 3, 7] {
+            let output = scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.flat_map(&nums, |num, output| {
+                    output.extend(std::iter::repeat_n(*num, num % 3));
+                })
+            });
+
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn partition_preserves_order() {
+        let strings = (0..1_000).map(|num| num.to_string()).collect::<Vec<_>>();
+
+        let expected = strings
+            .iter()
+            .cloned()
+            .partition::<Vec<_>, _>(|string| string.ends_with('7'));
+
+        for parallelism in [1, 3, 7] {
+            let output = scope(NonZeroUsize::new(parallelism), |scope| {
+                scope.partition(&strings, |string| string.ends_with('7'))
+            });
+
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn dedup_works() {
+        let mut nums = vec![1, 1, 1, 2, 2, 2, 3, 3];
+
+        let len = scope(NonZeroUsize::new(4), |scope| scope.dedup(&mut nums));
+
+        assert_eq!(nums[..len], [1, 2, 3]);
+
+        for parallelism in [1, 3, 7] {
+            for length in [0, 1, 10, 1_000] {
+                for run in [1, 3, 1_000] {
+                    let mut strings = (0..length)
+                        .map(|num: usize| (num / run).to_string())
+                        .collect::<Vec<_>>();
+
+                    let mut sorted = strings.clone();
+                    sorted.sort_unstable();
+
+                    let mut expected = strings.clone();
+                    expected.dedup();
+
+                    let len = scope(NonZeroUsize::new(parallelism), |scope| {
+                        scope.dedup(&mut strings)
+                    });
+
+                    assert_eq!(strings[..len], expected);
+
+                    strings.sort_unstable();
+                    assert_eq!(strings, sorted);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn retain_preserves_order() {
+        let preds: [fn(&usize) -> bool; 4] = [
+            |_num| false,
+            |_num| true,
+            |num| num % 2 == 0,
+            |num| num % 7 == 3,
+        ];
+
+        for parallelism in [1, 3, 7] {
+            for length in [0, 1, 10, 1_000] {
+                for pred in preds {
+                    let mut strings = (0..length).map(|num| num.to_string()).collect::<Vec<_>>();
+
+                    let len = scope(NonZeroUsize::new(parallelism), |scope| {
+                        scope.retain(&mut strings, |string| pred(&string.parse().unwrap()))
+                    });
+
+                    let (expected_kept, expected_rejected): (Vec<_>, Vec<_>) =
+                        (0..length).partition(pred);
+
+                    let (kept, rejected) = strings.split_at(len);
+
+                    let parse = |strings: &[String]| {
+                        strings
+                            .iter()
+                            .map(|string| string.parse::<usize>().unwrap())
+                            .collect::<Vec<_>>()
+                    };
+
+                    assert_eq!(parse(kept), expected_kept);
+                    assert_eq!(parse(rejected), expected_rejected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scan_inplace_works() {
+        for parallelism in [1, 3, 7] {
+            for length in [0, 1, 10, 10_000] {
+                let mut nums = (0..length).collect::<Vec<usize>>();
+
+                let expected = nums
+                    .iter()
+                    .scan(0, |sum, num| {
+                        *sum += num;
+                        Some(*sum)
+                    })
+                    .collect::<Vec<_>>();
+
+                scope(NonZeroUsize::new(parallelism), |scope| {
+                    scope.scan_inplace(&mut nums, |lhs, rhs| lhs + rhs);
+                });
+
+                assert_eq!(nums, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn scan_inplace_preserves_order() {
+        let mut strings = (0..100).map(|num| num.to_string()).collect::<Vec<_>>();
+
+        scope(NonZeroUsize::new(7), |scope| {
+            scope.scan_inplace(&mut strings, |lhs, rhs| format!("{lhs}{rhs}"));
+        });
+
+        let mut expected = String::new();
+
+        for (num, string) in strings.into_iter().enumerate() {
+            expected.push_str(&num.to_string());
+
+            assert_eq!(string, expected);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::cmp::Ordering;
+
+use crate::scope::Scope;
+
+const SEQUENTIAL_THRESHOLD: usize = 4 * 1024;
+
+impl Scope<'_> {
+    pub fn sort_unstable<T>(&self, data: &mut [T])
+    where
+        T: Ord + Send,
+    {
+        self.sort_unstable_by(data, T::cmp);
+    }
+
+    /// Recursively partitions `data` around its median in parallel
+    /// until there are enough pieces to sort them independently.
+    pub fn sort_unstable_by<T, F>(&self, data: &mut [T], cmp: F)
+    where
+        T: Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        if self.workers == 0 || data.len() <= SEQUENTIAL_THRESHOLD {
+            data.sort_unstable_by(cmp);
+            return;
+        }
+
+        let target = 4 * (self.workers + 1);
+
+        let mut pieces = vec![data];
+
+        while pieces.len() < target {
+            if pieces
+                .iter()
+                .all(|piece| piece.len() <= SEQUENTIAL_THRESHOLD)
+            {
+                break;
+            }
+
+            self.uncancellable().for_each_dynamic(&mut pieces, |piece| {
+                if piece.len() > SEQUENTIAL_THRESHOLD {
+                    piece.select_nth_unstable_by(piece.len() / 2, &cmp);
+                }
+            });
+
+            pieces = pieces
+                .into_iter()
+                .flat_map(|piece| {
+                    if piece.len() > SEQUENTIAL_THRESHOLD {
+                        let (lhs, rhs) = piece.split_at_mut(piece.len() / 2);
+
+                        [lhs, rhs]
+                    } else {
+                        [piece, Default::default()]
+                    }
+                })
+                .filter(|piece| !piece.is_empty())
+                .collect();
+        }
+
+        self.uncancellable().for_each_dynamic(&mut pieces, |piece| {
+            piece.sort_unstable_by(&cmp);
+        });
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use crate::scope::scope;
+
+    fn check<T>(mut data: Vec<T>)
+    where
+        T: Ord + Send + Clone + std::fmt::Debug,
+    {
+        let mut expected = data.clone();
+        expected.sort();
+
+        scope(NonZeroUsize::new(4), |scope| scope.sort_unstable(&mut data));
+
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn sort_unstable_works() {
+        let length = 100_000;
+
+        check((0..length).collect());
+        check((0..length).rev().collect());
+        check((0..length).map(|num| num % 3).collect());
+        check(vec![0; length]);
+
+        let mut state = 1_u64;
+
+        check(
+            (0..length)
+                .map(|_| {
+                    state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    state >> 33
+                })
+                .collect(),
+        );
+
+        check((0..100).rev().collect());
+        check(Vec::<usize>::new());
+    }
+
+    #[test]
+    fn sort_unstable_by_works() {
+        let mut data = (0..100_000).collect::<Vec<_>>();
+
+        scope(NonZeroUsize::new(4), |scope| {
+            scope.sort_unstable_by(&mut data, |lhs, rhs| rhs.cmp(lhs));
+        });
+
+        assert!(data.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+}
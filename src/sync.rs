@@ -0,0 +1,25 @@
+// The atomics and thread primitives used by the broadcast protocol between `broadcast_impl` and `State::worker`.
+//
+// Keeping them behind this module allows swapping in the model-checked types from `loom`
+// by building with `RUSTFLAGS="--cfg loom"`, c.f. the `loom_tests` module of `scope`.
+// The protocol relies on the following orderings:
+//
+// * `work` is a plain `Cell` which is written only while no worker reads it.
+//   It and the per-worker slots of `dispatched` are written before bumping `generation` using `Release`,
+//   which the workers load using `Acquire` before reading them.
+//   Workers whose slot does not match the new generation skip it without reading `work` or writing `done`.
+// * Each worker stores the generation into its own slot of `done` using `Release` after running its work,
+//   which the caller loads using `Acquire` before resetting `work`.
+// * Parking pairs the `SeqCst` fences in `State::park` and `State::unpark`.
+#[cfg(loom)]
+pub(crate) use loom::{
+    hint::spin_loop,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering, fence},
+    thread::{Thread, current as current_thread, park as park_thread, yield_now},
+};
+#[cfg(not(loom))]
+pub(crate) use std::{
+    hint::spin_loop,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering, fence},
+    thread::{Thread, current as current_thread, park as park_thread, yield_now},
+};
@@ -0,0 +1,349 @@
+use std::mem::{replace, transmute};
+use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
+use std::sync::atomic::{Ordering, fence};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::scope::{Scope, State};
+
+/// Joins a task started using [`Scope::spawn`].
+///
+/// Dropping the handle without joining it detaches the task
+/// which might then never run if no worker picks it up before the scope ends.
+pub struct TaskHandle<'scope, T> {
+    state: &'scope State,
+    packet: Arc<Packet<'static, T>>,
+}
+
+impl<T> TaskHandle<'_, T> {
+    /// Blocks until the task has finished and returns its result or resumes its panic.
+    ///
+    /// If no worker has picked up the task yet, it is run by the calling thread instead.
+    pub fn join(self) -> T {
+        let mut wait_count = 0;
+
+        loop {
+            let mut packet = self.packet.0.lock().unwrap();
+
+            match replace(&mut *packet, PacketState::Joined) {
+                PacketState::Pending(f) => {
+                    drop(packet);
+
+                    return f();
+                }
+                PacketState::Running => {
+                    *packet = PacketState::Running;
+                    drop(packet);
+
+                    self.state.wait(&mut wait_count);
+                }
+                PacketState::Done(Ok(result)) => return result,
+                PacketState::Done(Err(panic)) => resume_unwind(panic),
+                PacketState::Joined => unreachable!(),
+            }
+        }
+    }
+}
+
+impl<'scope> Scope<'scope> {
+    /// Hands `f` to an idle worker and returns immediately.
+    ///
+    /// There is only a single slot for pending tasks, so if it is still occupied by another task,
+    /// `f` is not dispatched at all and will run on the calling thread when the handle is joined.
+    ///
+    /// `f` must be `'static` as the handle could be leaked while `f` is still running.
+    /// Tasks which are still pending when the scope ends are dropped whereas running ones are waited for.
+    pub fn spawn<T, F>(&self, f: F) -> TaskHandle<'scope, T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let packet = Arc::new(Packet(Mutex::new(PacketState::Pending(Box::new(f)))));
+
+        if self.workers != 0 {
+            let mut task = self.state.task.lock().unwrap();
+
+            if task.is_none() {
+                *task = Some(packet.clone());
+
+                self.state.has_task.store(true, Ordering::Relaxed);
+
+                drop(task);
+
+                self.state.unpark();
+            }
+        }
+
+        TaskHandle {
+            state: self.state,
+            packet,
+        }
+    }
+}
+
+impl Scope<'_> {
+    /// Runs `f` on the given worker and blocks until it returns its result or resumes its panic.
+    ///
+    /// Only the given worker is woken up instead of broadcasting to all workers.
+    /// If `worker` is zero, `f` is run on the calling thread. Panics if `worker` is not smaller
+    /// than the parallelism of the scope.
+    pub fn run_on<F, T>(&self, worker: usize, f: F) -> T
+    where
+        F: FnOnce() -> T + Send,
+        T: Send,
+    {
+        assert!(
+            worker <= self.workers,
+            "worker {worker} is out of range for parallelism {}",
+            self.workers + 1
+        );
+
+        if worker == 0 {
+            return f();
+        }
+
+        let state = self.state;
+
+        let packet = Arc::new(Packet(Mutex::new(PacketState::Pending(Box::new(f)))));
+
+        {
+            let task: Arc<dyn Task + '_> = packet.clone();
+
+            // SAFETY: This function does not return before the packet is done and
+            // the worker has dropped its reference to the task.
+            let task = unsafe { transmute::<Arc<dyn Task + '_>, Arc<dyn Task>>(task) };
+
+            let mut target = state.targets[worker - 1].lock().unwrap();
+
+            // As `Scope` is neither `Send` nor `Sync`, calls to `run_on` cannot overlap.
+            debug_assert!(target.is_none());
+
+            *target = Some(task);
+
+            state.has_target[worker - 1].store(true, Ordering::Relaxed);
+        }
+
+        state.unpark_worker(worker);
+
+        let mut wait_count = 0;
+
+        loop {
+            // The worker holds its reference until it is done with the task.
+            if Arc::strong_count(&packet) == 1 {
+                // Pairs with the `Release` decrement of the strong count by the worker.
+                fence(Ordering::Acquire);
+
+                let mut packet = packet.0.lock().unwrap();
+
+                match replace(&mut *packet, PacketState::Joined) {
+                    PacketState::Done(Ok(result)) => return result,
+                    PacketState::Done(Err(panic)) => resume_unwind(panic),
+                    _ => unreachable!(),
+                }
+            }
+
+            state.wait(&mut wait_count);
+        }
+    }
+}
+
+impl State {
+    /// Runs the task targeted at the given worker, if any, and returns whether there was one.
+    pub(crate) fn run_target(&self, thread: usize) -> bool {
+        if !self.has_target[thread - 1].load(Ordering::Relaxed) {
+            return false;
+        }
+
+        let task = {
+            let mut target = self.targets[thread - 1].lock().unwrap();
+
+            self.has_target[thread - 1].store(false, Ordering::Relaxed);
+
+            target.take()
+        };
+
+        if let Some(task) = task {
+            task.run();
+        }
+
+        true
+    }
+
+    /// Runs the pending task, if any, and returns whether there was one.
+    pub(crate) fn run_task(&self) -> bool {
+        if !self.has_task.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        let task = {
+            let mut task = self.task.lock().unwrap();
+
+            let Some(task) = task.take() else {
+                return false;
+            };
+
+            self.has_task.store(false, Ordering::Relaxed);
+            self.running_tasks.fetch_add(1, Ordering::Relaxed);
+
+            task
+        };
+
+        task.run();
+        drop(task);
+
+        self.running_tasks.fetch_sub(1, Ordering::Release);
+
+        true
+    }
+
+    /// Drops the pending task, if any, and waits for running tasks to finish.
+    pub(crate) fn drain_tasks(&self) {
+        {
+            let mut task = self.task.lock().unwrap();
+
+            self.has_task.store(false, Ordering::Relaxed);
+
+            drop(task.take());
+        }
+
+        let mut wait_count = 0;
+
+        while self.running_tasks.load(Ordering::Acquire) != 0 {
+            self.wait(&mut wait_count);
+        }
+    }
+}
+
+pub(crate) type TaskSlot = Mutex<Option<Arc<dyn Task>>>;
+
+pub(crate) trait Task: Send + Sync {
+    fn run(&self);
+}
+
+struct Packet<'a, T>(Mutex<PacketState<'a, T>>);
+
+enum PacketState<'a, T> {
+    Pending(Box<dyn FnOnce() -> T + Send + 'a>),
+    Running,
+    Done(thread::Result<T>),
+    Joined,
+}
+
+impl<T> Task for Packet<'_, T>
+where
+    T: Send,
+{
+    fn run(&self) {
+        let f = {
+            let mut packet = self.0.lock().unwrap();
+
+            match replace(&mut *packet, PacketState::Running) {
+                PacketState::Pending(f) => f,
+                other => {
+                    *packet = other;
+                    return;
+                }
+            }
+        };
+
+        let result = catch_unwind(AssertUnwindSafe(f));
+
+        *self.0.lock().unwrap() = PacketState::Done(result);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::panic::AssertUnwindSafe;
+    use std::sync::Arc;
+    use std::thread;
+
+    use crate::Config;
+    use crate::scope::{scope, scope_with};
+
+    #[test]
+    fn spawned_tasks_can_be_joined() {
+        let nums = Arc::new((0..1_000).collect::<Vec<usize>>());
+
+        let (sum, product) = scope(NonZeroUsize::new(2), |scope| {
+            let sum = scope.spawn({
+                let nums = nums.clone();
+
+                move || nums.iter().sum::<usize>()
+            });
+            let product = scope.spawn({
+                let nums = nums.clone();
+
+                move || nums.iter().map(|num| num % 7).product::<usize>()
+            });
+
+            scope.broadcast(|_thread| ());
+
+            (sum.join(), product.join())
+        });
+
+        assert_eq!(sum, 1_000 * 999 / 2);
+        assert_eq!(product, 0);
+    }
+
+    #[test]
+    fn tasks_run_on_caller_without_workers() {
+        let caller = thread::current().id();
+
+        let thread = scope(NonZeroUsize::new(1), |scope| {
+            scope.spawn(|| thread::current().id()).join()
+        });
+
+        assert_eq!(thread, caller);
+    }
+
+    #[test]
+    fn run_on_lands_on_named_worker() {
+        for park in [false, true] {
+            let config = Config {
+                parallelism: NonZeroUsize::new(4),
+                park,
+                ..Default::default()
+            };
+
+            scope_with(config, |scope| {
+                let threads = scope.broadcast_with(|_thread| thread::current().id());
+
+                let mut visited = Vec::new();
+
+                for worker in [2, 0, 3, 1, 3] {
+                    let thread = scope.run_on(worker, || {
+                        visited.push(worker);
+
+                        thread::current().id()
+                    });
+
+                    assert_eq!(thread, threads[worker]);
+                }
+
+                assert_eq!(visited, [2, 0, 3, 1, 3]);
+
+                let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                    scope.run_on(4, || unreachable!());
+                }));
+
+                assert!(result.is_err());
+            });
+        }
+    }
+
+    #[test]
+    fn task_panic_propagates_on_join() {
+        let result = std::panic::catch_unwind(|| {
+            scope(NonZeroUsize::new(2), |scope| {
+                scope.spawn(|| panic!("task panicked")).join();
+            });
+        });
+
+        assert_eq!(
+            *result.unwrap_err().downcast::<&str>().unwrap(),
+            "task panicked"
+        );
+    }
+}